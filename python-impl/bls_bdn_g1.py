@@ -0,0 +1,58 @@
+#!/usr/bin/python
+
+from bls_sig_g1 import keygen, sign, verify
+from functools import reduce
+import hashlib
+
+from consts import g1bdn, g1bdn_coef, q
+from curve_ops import point_add, point_mul
+from hash_to_field import expand_message_xmd, hash_to_field, I2OSP
+from serdesZ import serialize
+from util import get_cmdline_options, print_g1_hex, print_g2_hex, print_tv_bdn
+
+# the number of signers in each test vector: their keys come from sk || I2OSP(i, 1)
+BDN_SIGNERS = 3
+
+# the coefficient of each public key in pks, under the coefficient ciphersuite id:
+#     d = expand_message_xmd(pk_1 || ... || pk_n, 32)
+#     t_i = hash_to_field(pk_i || d) in the scalar field
+# with every public key serialized in compressed form
+def bdn_coefficients(pks, ciphersuite):
+    pks_bytes = [ serialize(pk, True) for pk in pks ]
+    digest = expand_message_xmd(b''.join(pks_bytes), ciphersuite, 32, hashlib.sha256)
+    return [ hash_to_field(pk_bytes + digest, 1, ciphersuite, q, 1, 48, expand_message_xmd, hashlib.sha256)[0][0] \
+             for pk_bytes in pks_bytes ]
+
+# the aggregate public key: sum_i t_i * pk_i
+def bdn_aggregate_pks(pks, coef_suite):
+    ts = bdn_coefficients(pks, coef_suite)
+    return reduce(point_add, ( point_mul(t, pk) for (t, pk) in zip(ts, pks) ))
+
+# the multisignature on msg by every key in keys, a list of (x_prime, pk) pairs.
+# sum_i t_i * sign(x_prime_i, msg) is the same as sign(sum_i t_i * x_prime_i, msg)
+def _bdn_multisign(keys, msg, ciphersuite, coef_suite, sign_fn):
+    ts = bdn_coefficients([ pk for (_, pk) in keys ], coef_suite)
+    x_agg = sum( t * x_prime for (t, (x_prime, _)) in zip(ts, keys) ) % q
+    return sign_fn(x_agg, msg, ciphersuite)
+
+def bdn_multisign(keys, msg, ciphersuite, coef_suite=g1bdn_coef):
+    return _bdn_multisign(keys, msg, ciphersuite, coef_suite, sign)
+
+# verify a multisignature by every key in pks
+def _bdn_multisig_verify(pks, sig, msg, ciphersuite, coef_suite, ver_fn):
+    return ver_fn(bdn_aggregate_pks(pks, coef_suite), sig, msg, ciphersuite)
+
+def bdn_multisig_verify(pks, sig, msg, ciphersuite, coef_suite=g1bdn_coef):
+    return _bdn_multisig_verify(pks, sig, msg, ciphersuite, coef_suite, verify)
+
+# the keys of the signers for a test vector with secret sk
+def bdn_keys(sk, keygen_fn=keygen):
+    return [ keygen_fn(sk + I2OSP(idx, 1)) for idx in range(0, BDN_SIGNERS) ]
+
+if __name__ == "__main__":
+    def main():
+        opts = get_cmdline_options()
+        ver_fn = bdn_multisig_verify if opts.verify else None
+        for sig_in in opts.test_inputs:
+            print_tv_bdn(sig_in, g1bdn, bdn_multisign, bdn_keys, print_g2_hex, print_g1_hex, ver_fn, False, opts)
+    main()
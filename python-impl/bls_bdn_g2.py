@@ -0,0 +1,20 @@
+#!/usr/bin/python
+
+from bls_bdn_g1 import _bdn_multisign, _bdn_multisig_verify, bdn_keys as _bdn_keys_g1
+from bls_sig_g2 import keygen, sign, verify
+from functools import partial
+
+from consts import g2bdn, g2bdn_coef
+from util import get_cmdline_options, print_g1_hex, print_g2_hex, print_tv_bdn
+
+bdn_multisign = partial(_bdn_multisign, coef_suite=g2bdn_coef, sign_fn=sign)
+bdn_multisig_verify = partial(_bdn_multisig_verify, coef_suite=g2bdn_coef, ver_fn=verify)
+bdn_keys = partial(_bdn_keys_g1, keygen_fn=keygen)
+
+if __name__ == "__main__":
+    def main():
+        opts = get_cmdline_options()
+        ver_fn = bdn_multisig_verify if opts.verify else None
+        for sig_in in opts.test_inputs:
+            print_tv_bdn(sig_in, g2bdn, bdn_multisign, bdn_keys, print_g1_hex, print_g2_hex, ver_fn, True, opts)
+    main()
@@ -3,10 +3,10 @@
 from functools import partial, reduce
 from itertools import chain
 
-from consts import g1suite
+from consts import g1suite, g1suite_xof
 from curve_ops import g2gen, point_add, point_mul, point_neg, subgroup_check_g1, subgroup_check_g2
 from hash_to_field import xprime_from_sk
-from opt_swu_g1 import map2curve_osswu
+from opt_swu_g1 import map2curve_osswu, map2curve_osswu_shake
 from pairing import multi_pairing
 from serdesZ import serialize
 from util import get_cmdline_options, print_g1_hex, print_g2_hex, print_tv_sig, SigType
@@ -35,8 +35,8 @@ sign_aug = partial(_sign_aug, gen=g2gen, sign_fn=sign)
 # verification corresponding to sign()
 # returns True if the signature is correct, False otherwise
 # NOTE: if pk has been verified to be in correct subgroup, do not need to recheck here
-def verify(pk, sig, msg, ciphersuite):
-    P = map2curve_osswu(msg, ciphersuite)
+def verify(pk, sig, msg, ciphersuite, map_fn=map2curve_osswu):
+    P = map_fn(msg, ciphersuite)
     if not (subgroup_check_g2(pk) and subgroup_check_g1(sig)):
         return False
     return multi_pairing((P, sig), (pk, point_neg(g2gen))) == 1
@@ -85,14 +85,20 @@ aggregate_verify_aug = partial(_agg_ver_aug, ver_fn=aggregate_verify)
 if __name__ == "__main__":
     def main():
         opts = get_cmdline_options()
+        if opts.xof:
+            sign_base = partial(_sign, map_fn=map2curve_osswu_shake)
+            verify_base = partial(verify, map_fn=map2curve_osswu_shake)
+        else:
+            sign_base = sign
+            verify_base = verify
         if opts.sigtype == SigType.message_augmentation:
-            sig_fn = sign_aug
-            ver_fn = verify_aug
+            sig_fn = partial(_sign_aug, gen=g2gen, sign_fn=sign_base)
+            ver_fn = partial(_verify_aug, ver_fn=verify_base)
         else:
-            sig_fn = sign
-            ver_fn = verify
+            sig_fn = sign_base
+            ver_fn = verify_base
         ver_fn = ver_fn if opts.verify else None
-        csuite = g1suite(opts.sigtype)
+        csuite = g1suite_xof(opts.sigtype) if opts.xof else g1suite(opts.sigtype)
         for sig_in in opts.test_inputs:
             print_tv_sig(sig_in, csuite, sig_fn, keygen, print_g2_hex, print_g1_hex, ver_fn, False, opts)
     main()
@@ -4,9 +4,9 @@ from functools import partial
 from itertools import chain
 
 from bls_sig_g1 import _agg_ver_nul, _agg_ver_aug, _keygen, _sign, _sign_aug, _verify_aug
-from consts import g2suite
+from consts import g2suite, g2suite_xof
 from curve_ops import g1gen, point_neg, subgroup_check_g1, subgroup_check_g2
-from opt_swu_g2 import map2curve_osswu2
+from opt_swu_g2 import map2curve_osswu2, map2curve_osswu2_shake
 from pairing import multi_pairing
 from util import get_cmdline_options, print_g1_hex, print_g2_hex, print_tv_sig, SigType
 
@@ -23,8 +23,8 @@ sign_aug = partial(_sign_aug, gen=g1gen, sign_fn=sign)
 # verification corresponding to sign()
 # returns True if the signature is correct, False otherwise
 # NOTE: if pk has been verified to be in correct subgroup, do not need to recheck here
-def verify(pk, sig, msg, ciphersuite):
-    P = map2curve_osswu2(msg, ciphersuite)
+def verify(pk, sig, msg, ciphersuite, map_fn=map2curve_osswu2):
+    P = map_fn(msg, ciphersuite)
     if not (subgroup_check_g1(pk) and subgroup_check_g2(sig)):
         return False
     return multi_pairing((pk, point_neg(g1gen)), (P, sig)) == 1
@@ -55,14 +55,20 @@ aggregate_verify_aug = partial(_agg_ver_aug, ver_fn=aggregate_verify)
 if __name__ == "__main__":
     def main():
         opts = get_cmdline_options()
+        if opts.xof:
+            sign_base = partial(_sign, map_fn=map2curve_osswu2_shake)
+            verify_base = partial(verify, map_fn=map2curve_osswu2_shake)
+        else:
+            sign_base = sign
+            verify_base = verify
         if opts.sigtype == SigType.message_augmentation:
-            sig_fn = sign_aug
-            ver_fn = verify_aug
+            sig_fn = partial(_sign_aug, gen=g1gen, sign_fn=sign_base)
+            ver_fn = partial(_verify_aug, ver_fn=verify_base)
         else:
-            sig_fn = sign
-            ver_fn = verify
+            sig_fn = sign_base
+            ver_fn = verify_base
         ver_fn = ver_fn if opts.verify else None
-        csuite = g2suite(opts.sigtype)
+        csuite = g2suite_xof(opts.sigtype) if opts.xof else g2suite(opts.sigtype)
         for sig_in in opts.test_inputs:
             print_tv_sig(sig_in, csuite, sig_fn, keygen, print_g1_hex, print_g2_hex, ver_fn, True, opts)
     main()
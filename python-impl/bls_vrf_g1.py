@@ -0,0 +1,40 @@
+#!/usr/bin/python
+
+from bls_sig_g1 import keygen, sign, verify
+import hashlib
+
+from consts import g1vrf, g1vrf_sig
+from hash_to_field import expand_message_xmd
+from serdesZ import serialize
+from util import get_cmdline_options, print_g1_hex, print_g2_hex, print_tv_vrf
+
+# the output for a proof, which is a signature under the VRF signing ciphersuite id:
+#     beta = expand_message_xmd(proof, 32)
+# under the VRF ciphersuite id, with the proof serialized in compressed form
+def proof_to_hash(proof, vrf_suite):
+    return expand_message_xmd(serialize(proof, True), vrf_suite, 32, hashlib.sha256)
+
+# the output beta and the proof for input alpha
+def _vrf_prove(x_prime, alpha, ciphersuite, vrf_suite, sign_fn):
+    proof = sign_fn(x_prime, alpha, ciphersuite)
+    return (proof_to_hash(proof, vrf_suite), proof)
+
+def vrf_prove(x_prime, alpha, ciphersuite, vrf_suite=g1vrf):
+    return _vrf_prove(x_prime, alpha, ciphersuite, vrf_suite, sign)
+
+# the output beta if proof is valid for alpha under pk, else None
+def _vrf_verify(pk, alpha, proof, ciphersuite, vrf_suite, ver_fn):
+    if not ver_fn(pk, proof, alpha, ciphersuite):
+        return None
+    return proof_to_hash(proof, vrf_suite)
+
+def vrf_verify(pk, alpha, proof, ciphersuite, vrf_suite=g1vrf):
+    return _vrf_verify(pk, alpha, proof, ciphersuite, vrf_suite, verify)
+
+if __name__ == "__main__":
+    def main():
+        opts = get_cmdline_options()
+        ver_fn = vrf_verify if opts.verify else None
+        for vrf_in in opts.test_inputs:
+            print_tv_vrf(vrf_in, g1vrf_sig, g1vrf, vrf_prove, keygen, print_g2_hex, print_g1_hex, ver_fn, False, opts)
+    main()
@@ -0,0 +1,19 @@
+#!/usr/bin/python
+
+from bls_sig_g2 import keygen, sign, verify
+from bls_vrf_g1 import _vrf_prove, _vrf_verify
+from functools import partial
+
+from consts import g2vrf, g2vrf_sig
+from util import get_cmdline_options, print_g1_hex, print_g2_hex, print_tv_vrf
+
+vrf_prove = partial(_vrf_prove, sign_fn=sign)
+vrf_verify = partial(_vrf_verify, ver_fn=verify)
+
+if __name__ == "__main__":
+    def main():
+        opts = get_cmdline_options()
+        ver_fn = vrf_verify if opts.verify else None
+        for vrf_in in opts.test_inputs:
+            print_tv_vrf(vrf_in, g2vrf_sig, g2vrf, vrf_prove, keygen, print_g1_hex, print_g2_hex, ver_fn, True, opts)
+    main()
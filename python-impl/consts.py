@@ -12,9 +12,26 @@ q = 0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001
 k_final = (p ** 4 - p ** 2 + 1) // q
 
 # ciphersuite numbers
-_gsuite = lambda stype, group, stag: b'BLS_' + stype + b'_BLS12381G' + group + \
-                                     b'_XMD:SHA-256_SSWU_RO_' + bytes(stag) + b'_'
+_gsuite = lambda stype, group, stag, htag=b'XMD:SHA-256': b'BLS_' + stype + b'_BLS12381G' + group + \
+                                     b'_' + htag + b'_SSWU_RO_' + bytes(stag) + b'_'
 g1suite = lambda stag: _gsuite(b'SIG', b'1', stag)
 g1pop = _gsuite(b'POP', b'1', b'POP')
 g2suite = lambda stag: _gsuite(b'SIG', b'2', stag)
 g2pop = _gsuite(b'POP', b'2', b'POP')
+# ciphersuites using expand_message_xof with SHAKE-256
+g1suite_xof = lambda stag: _gsuite(b'SIG', b'1', stag, b'XOF:SHAKE-256')
+g1pop_xof = _gsuite(b'POP', b'1', b'POP', b'XOF:SHAKE-256')
+g2suite_xof = lambda stag: _gsuite(b'SIG', b'2', stag, b'XOF:SHAKE-256')
+g2pop_xof = _gsuite(b'POP', b'2', b'POP', b'XOF:SHAKE-256')
+# ciphersuites for multisignatures in the style of Boneh-Drijvers-Neven: the tag for signing
+# and the tag for hashing the public keys to coefficients
+g1bdn = _gsuite(b'SIG', b'1', b'BDN')
+g1bdn_coef = _gsuite(b'COEF', b'1', b'BDN')
+g2bdn = _gsuite(b'SIG', b'2', b'BDN')
+g2bdn_coef = _gsuite(b'COEF', b'2', b'BDN')
+# ciphersuites for the BLS VRF: the tag for signing to make a proof
+# and the tag for hashing a proof to the output
+g1vrf_sig = _gsuite(b'SIG', b'1', b'VRF')
+g1vrf = _gsuite(b'VRF', b'1', b'NUL')
+g2vrf_sig = _gsuite(b'SIG', b'2', b'VRF')
+g2vrf = _gsuite(b'VRF', b'2', b'NUL')
@@ -98,12 +98,12 @@ def hash_to_field(msg, count, DST, modulus, degree, blen, expand_fn, hash_fn):
 def Hp_shake(msg, count, dst):
     if not isinstance(msg, bytes):
         raise ValueError("Hp can't hash anything but bytes")
-    return hash_to_field(msg, count, dst, p, 1, 64, expand_message_xof, hashlib.shake_128)
+    return hash_to_field(msg, count, dst, p, 1, 64, expand_message_xof, hashlib.shake_256)
 
 def Hp2_shake(msg, count, dst):
     if not isinstance(msg, bytes):
         raise ValueError("Hp2 can't hash anything but bytes")
-    return hash_to_field(msg, count, dst, p, 2, 64, expand_message_xof, hashlib.shake_128)
+    return hash_to_field(msg, count, dst, p, 2, 64, expand_message_xof, hashlib.shake_256)
 
 def Hp(msg, count, dst):
     if not isinstance(msg, bytes):
@@ -115,6 +115,7 @@ def Hp2(msg, count, dst):
         raise ValueError("Hp2 can't hash anything but bytes")
     return hash_to_field(msg, count, dst, p, 2, 64, expand_message_xmd, hashlib.sha256)
 
+# legacy KeyGen: single HKDF invocation with a fixed salt
 def xprime_from_sk(msg, key_info=None):
     if not isinstance(msg, bytes):
         raise ValueError("xprime_from_sk can't hash anything but bytes")
@@ -124,6 +125,23 @@ def xprime_from_sk(msg, key_info=None):
     okm = hkdf_expand(prk, key_info + I2OSP(48, 2), 48, hashlib.sha256)
     return OS2IP(okm) % q
 
+# KeyGen from draft-irtf-cfrg-bls-signature-04
+def KeyGen(IKM, key_info=None):
+    if not isinstance(IKM, bytes):
+        raise ValueError("KeyGen can't hash anything but bytes")
+    if len(IKM) < 32:
+        raise ValueError("KeyGen requires at least 32 bytes of IKM")
+    if key_info is None:
+        key_info = b''
+    salt = b"BLS-SIG-KEYGEN-SALT-"
+    SK = 0
+    while SK == 0:
+        salt = hashlib.sha256(salt).digest()
+        prk = hkdf_extract(salt, IKM + I2OSP(0, 1), hashlib.sha256)
+        okm = hkdf_expand(prk, key_info + I2OSP(48, 2), 48, hashlib.sha256)
+        SK = OS2IP(okm) % q
+    return SK
+
 def _random_string(strlen):
     return bytes( randint(65, 65 + 25) for _ in range(0, strlen) )
 
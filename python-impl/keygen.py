@@ -0,0 +1,50 @@
+#!/usr/bin/python
+#
+# generate or check KeyGen test vectors
+
+from binascii import hexlify, unhexlify
+import getopt
+import sys
+
+from hash_to_field import I2OSP, KeyGen, xprime_from_sk
+
+def main():
+    try:
+        (opts, args) = getopt.gnu_getopt(sys.argv[1:], "lgT:")
+    except getopt.GetoptError as err:
+        print("Usage: %s [-l] [-g] -T test_file ..." % sys.argv[0])
+        sys.exit(str(err))
+
+    legacy = False
+    gen_vectors = False
+    test_inputs = []
+    for (opt, arg) in opts:
+        if opt == "-l":
+            legacy = True
+        elif opt == "-g":
+            gen_vectors = True
+        elif opt == "-T":
+            with open(arg, "r") as test_file:
+                test_inputs += [ tuple( unhexlify(val) for val in line.strip().split(' ') ) \
+                                 for line in test_file.readlines() ]
+        else:
+            raise RuntimeError("got unexpected option %s from getopt" % opt)
+
+    # each test input is (key_info, IKM[, SK])
+    for test_in in test_inputs:
+        (key_info, ikm) = test_in[:2]
+        if legacy:
+            sk = xprime_from_sk(ikm, key_info)
+        elif len(ikm) < 32:
+            continue
+        else:
+            sk = KeyGen(ikm, key_info)
+        sk = I2OSP(sk, 32)
+
+        if gen_vectors:
+            print(b' '.join( hexlify(v) for v in (key_info, ikm, sk) ).decode('ascii'))
+        elif len(test_in) > 2 and test_in[2] != sk:
+            raise RuntimeError("KeyGen did not give expected SK")
+
+if __name__ == "__main__":
+    main()
@@ -5,7 +5,7 @@
 from consts import p
 from curve_ops import clear_h, eval_iso, from_jacobian, point_add
 from fields import Fq, sgn0
-from hash_to_field import Hp
+from hash_to_field import Hp, Hp_shake
 from util import get_cmdline_options, print_g1_hex, print_tv_hash
 
 # distinguished non-square in Fp for SWU map
@@ -132,6 +132,10 @@ def opt_swu_map(t, t2=None):
 def map2curve_osswu(alpha, dst=None):
     return opt_swu_map( *( Fq(p, *hh) for hh in Hp(alpha, 2, dst) ) )
 
+# same as above, but hash_to_field uses expand_message_xof with SHAKE-256
+def map2curve_osswu_shake(alpha, dst=None):
+    return opt_swu_map( *( Fq(p, *hh) for hh in Hp_shake(alpha, 2, dst) ) )
+
 if __name__ == "__main__":
     import sys
 
@@ -5,7 +5,7 @@
 from consts import p
 from curve_ops import clear_h2, eval_iso, from_jacobian, point_add
 from fields import Fq2, sgn0, roots_of_unity
-from hash_to_field import Hp2
+from hash_to_field import Hp2, Hp2_shake
 from util import get_cmdline_options, print_g2_hex, print_tv_hash
 
 # distinguished non-square in Fp2 for SWU map
@@ -131,6 +131,10 @@ def opt_swu2_map(t, t2=None):
 def map2curve_osswu2(alpha, dst=None):
     return opt_swu2_map( *( Fq2(p, *hh) for hh in Hp2(alpha, 2, dst) ) )
 
+# same as above, but hash_to_field uses expand_message_xof with SHAKE-256
+def map2curve_osswu2_shake(alpha, dst=None):
+    return opt_swu2_map( *( Fq2(p, *hh) for hh in Hp2_shake(alpha, 2, dst) ) )
+
 if __name__ == "__main__":
     import sys
 
@@ -34,6 +34,7 @@ class Options(object):
     verify = False
     quiet = False
     gen_vectors = False
+    xof = False
     sigtype = SigType.basic
 
     def __init__(self):
@@ -52,10 +53,10 @@ def get_cmdline_options():
 
     # process cmdline args with getopt
     try:
-        (opts, args) = getopt.gnu_getopt(sys.argv[1:], "k:T:tvqBAPg")
+        (opts, args) = getopt.gnu_getopt(sys.argv[1:], "k:T:tvqBAPgx")
 
     except getopt.GetoptError as err:
-        print("Usage: %s [-gqtv] [-k key] [-T test_file] [-B | -A | -P] [msg ...]" % sys.argv[0])
+        print("Usage: %s [-gqtvx] [-k key] [-T test_file] [-B | -A | -P] [msg ...]" % sys.argv[0])
         sys.exit(str(err))
 
     for (opt, arg) in opts:
@@ -86,6 +87,9 @@ def get_cmdline_options():
         elif opt == "-g":
             ret.gen_vectors = True
 
+        elif opt == "-x":
+            ret.xof = True
+
         else:
             raise RuntimeError("got unexpected option %s from getopt" % opt)
 
@@ -279,3 +283,120 @@ def print_tv_pop(sig_in, ciphersuite, sign_fn, keygen_fn, print_pk_fn, print_sig
     print_sig_fn(sig)
 
     print("==================  end test vector  ====================")
+
+def print_tv_bdn(sig_in, ciphersuite, multisign_fn, keys_fn, print_pk_fn, print_sig_fn, ver_fn, is_ell2, opts):
+    if len(sig_in) > 2:
+        (msg, sk, sig_expect) = sig_in[:3]
+    else:
+        (msg, sk) = sig_in
+        sig_expect = None
+    # generate keys and multisignature
+    keys = keys_fn(sk)
+    pks = [ pk for (_, pk) in keys ]
+    sig = multisign_fn(keys, msg, ciphersuite)
+
+    if ver_fn is not None and not ver_fn(pks, sig, msg, ciphersuite):
+        raise RuntimeError("verifying generated multisignature failed")
+
+    if opts.gen_vectors:
+        print(b' '.join( hexlify(v) for v in (msg, sk, serialize(sig)) ).decode('ascii'))
+        return
+
+    if sig_expect is not None:
+        if serialize(sig) != sig_expect:
+            raise SerError("serializing sig did not give sig_expect")
+        if from_jacobian(deserialize(sig_expect, is_ell2)) != from_jacobian(sig):
+            raise DeserError("deserializing sig_expect did not give sig")
+
+    if opts.quiet:
+        return
+
+    # output the test vector
+    print("================== begin test vector ====================")
+
+    print("g1 generator:")
+    print_g1_hex(g1gen)
+
+    print("g2 generator:")
+    print_g2_hex(g2gen)
+
+    print("group order: 0x%x" % q)
+    sys.stdout.write("ciphersuite: ")
+    print_value(ciphersuite, 13, True)
+
+    sys.stdout.write("message:     ")
+    print_value(msg, 13, True)
+
+    sys.stdout.write("sk:          ")
+    print_value(sk, 13, True)
+
+    for (idx, pk) in enumerate(pks):
+        print("public key %d:" % idx)
+        print_pk_fn(pk)
+
+    print("signature:")
+    print_sig_fn(sig)
+
+    print("==================  end test vector  ====================")
+
+def print_tv_vrf(vrf_in, ciphersuite, vrf_suite, prove_fn, keygen_fn, print_pk_fn, print_sig_fn, ver_fn, is_ell2, opts):
+    if len(vrf_in) > 2:
+        (alpha, sk, expect) = vrf_in[:3]
+    else:
+        (alpha, sk) = vrf_in
+        expect = None
+    # generate key, output, and proof
+    (x_prime, pk) = keygen_fn(sk)
+    (beta, proof) = prove_fn(x_prime, alpha, ciphersuite, vrf_suite)
+
+    if ver_fn is not None and ver_fn(pk, alpha, proof, ciphersuite, vrf_suite) != beta:
+        raise RuntimeError("verifying generated proof failed")
+
+    if opts.gen_vectors:
+        print(b' '.join( hexlify(v) for v in (alpha, sk, serialize(proof) + beta) ).decode('ascii'))
+        return
+
+    if expect is not None:
+        if serialize(proof) + beta != expect:
+            raise SerError("serializing proof and beta did not give expect")
+        if from_jacobian(deserialize(expect[:-32], is_ell2)) != from_jacobian(proof):
+            raise DeserError("deserializing the proof in expect did not give proof")
+
+    if opts.quiet:
+        return
+
+    # output the test vector
+    print("================== begin test vector ====================")
+
+    print("g1 generator:")
+    print_g1_hex(g1gen)
+
+    print("g2 generator:")
+    print_g2_hex(g2gen)
+
+    print("group order: 0x%x" % q)
+    sys.stdout.write("ciphersuite: ")
+    print_value(ciphersuite, 13, True)
+
+    sys.stdout.write("vrf suite:   ")
+    print_value(vrf_suite, 13, True)
+
+    sys.stdout.write("alpha:       ")
+    print_value(alpha, 13, True)
+
+    sys.stdout.write("sk:          ")
+    print_value(sk, 13, True)
+
+    sys.stdout.write("x_prime:     ")
+    print_value(x_prime, 13, True)
+
+    print("public key:")
+    print_pk_fn(pk)
+
+    print("proof:")
+    print_sig_fn(proof)
+
+    sys.stdout.write("beta:        ")
+    print_value(beta, 13, True)
+
+    print("==================  end test vector  ====================")
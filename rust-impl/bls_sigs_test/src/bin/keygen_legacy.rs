@@ -0,0 +1,13 @@
+extern crate bls_sigs_ref;
+extern crate bls_sigs_test;
+
+use bls_sigs_ref::KeyGenMode;
+use bls_sigs_test::{get_vecs, test_keygen};
+use std::io::Result;
+
+fn main() -> Result<()> {
+    for vec in get_vecs("keygen_legacy")? {
+        test_keygen(vec?, KeyGenMode::Legacy)?;
+    }
+    Ok(())
+}
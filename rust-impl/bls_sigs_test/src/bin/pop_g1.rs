@@ -8,7 +8,7 @@ use std::io::Result;
 
 fn main() -> Result<()> {
     for vec in get_vecs("pop_g1")? {
-        test_pop::<G1>(vec?, 48)?;
+        test_pop::<G1>(vec?)?;
     }
     Ok(())
 }
@@ -0,0 +1,13 @@
+extern crate bls_sigs_ref;
+extern crate bls_sigs_test;
+
+use bls_sigs_ref::G2XofShake256Pop;
+use bls_sigs_test::{get_vecs, test_pop_suite};
+use std::io::Result;
+
+fn main() -> Result<()> {
+    for vec in get_vecs("pop_g2_xof")? {
+        test_pop_suite::<G2XofShake256Pop>(vec?)?;
+    }
+    Ok(())
+}
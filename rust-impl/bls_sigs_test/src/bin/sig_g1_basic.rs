@@ -8,7 +8,7 @@ use std::io::Result;
 
 fn main() -> Result<()> {
     for vec in get_vecs("sig_g1_basic")? {
-        test_sig_basic::<G1>(vec?, 48)?;
+        test_sig_basic::<G1>(vec?)?;
     }
     Ok(())
 }
@@ -0,0 +1,13 @@
+extern crate bls_sigs_ref;
+extern crate bls_sigs_test;
+
+use bls_sigs_ref::G1XofShake256Basic;
+use bls_sigs_test::{get_vecs, test_sig_suite};
+use std::io::Result;
+
+fn main() -> Result<()> {
+    for vec in get_vecs("sig_g1_basic_xof")? {
+        test_sig_suite::<G1XofShake256Basic>(vec?)?;
+    }
+    Ok(())
+}
@@ -8,7 +8,7 @@ use std::io::Result;
 
 fn main() -> Result<()> {
     for vec in get_vecs("sig_g2_pop")? {
-        test_sig_pop::<G2>(vec?, 96)?;
+        test_sig_pop::<G2>(vec?)?;
     }
     Ok(())
 }
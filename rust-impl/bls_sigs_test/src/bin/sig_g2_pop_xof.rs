@@ -0,0 +1,13 @@
+extern crate bls_sigs_ref;
+extern crate bls_sigs_test;
+
+use bls_sigs_ref::G2XofShake256Pop;
+use bls_sigs_test::{get_vecs, test_sig_suite};
+use std::io::Result;
+
+fn main() -> Result<()> {
+    for vec in get_vecs("sig_g2_pop_xof")? {
+        test_sig_suite::<G2XofShake256Pop>(vec?)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,14 @@
+extern crate bls_sigs_ref;
+extern crate bls_sigs_test;
+extern crate pairing_plus;
+
+use bls_sigs_test::{get_vecs, test_vrf};
+use pairing_plus::bls12_381::G2;
+use std::io::Result;
+
+fn main() -> Result<()> {
+    for vec in get_vecs("vrf_g2")? {
+        test_vrf::<G2>(vec?)?;
+    }
+    Ok(())
+}
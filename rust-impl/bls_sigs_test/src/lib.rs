@@ -1,5 +1,5 @@
-#![cfg_attr(feature = "cargo-clippy", deny(warnings))]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::unreadable_literal))]
+#![cfg_attr(clippy, deny(warnings))]
+#![allow(clippy::unreadable_literal)]
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 
@@ -15,15 +15,24 @@ extern crate sha2;
 mod test;
 mod testvec;
 
-use bls_sigs_ref::{BLSSignatureAug, BLSSignatureBasic, BLSSignaturePop};
+use bls_sigs_ref::{
+    key_gen, sk_from_bytes, sk_to_bytes, BLSSignatureAug, BLSSignatureBasic, BLSSignatureBdn,
+    BLSSignaturePop, BLSVrf, BlsError, Ciphersuite, KeyGenMode, PointEncoding, PopCiphersuite,
+    SigT,
+};
 use pairing_plus::hash_to_curve::HashToCurve;
 use pairing_plus::hash_to_field::ExpandMsgXmd;
 use pairing_plus::serdes::SerDes;
 use pairing_plus::CurveProjective;
-use std::io::{Cursor, Result};
 use sha2::Sha256;
+use std::io::{Cursor, Error, ErrorKind, Result};
 pub use testvec::{get_dflt_vecs, get_vecs, TestVector};
 
+// report library errors through the io::Result used by the test harness
+fn _to_io(e: BlsError) -> Error {
+    Error::new(ErrorKind::InvalidData, e)
+}
+
 /// Test hash function
 pub fn test_hash<G>(tests: Vec<TestVector>, ciphersuite: &[u8], len: usize) -> Result<()>
 where
@@ -50,9 +59,9 @@ where
 }
 
 /// Test sign functionality for Basic
-pub fn test_sig_basic<G>(tests: Vec<TestVector>, len: usize) -> Result<()>
+pub fn test_sig_basic<G>(tests: Vec<TestVector>) -> Result<()>
 where
-    G: BLSSignatureBasic<ExpandMsgXmd<Sha256>> + CurveProjective + SerDes,
+    G: BLSSignatureBasic<ExpandMsgXmd<Sha256>> + PointEncoding,
 {
     for TestVector { msg, sk, expect } in tests {
         let (x_prime, pk) = G::keygen(sk);
@@ -61,14 +70,10 @@ where
         match expect {
             None => println!("{:?}", sig),
             Some(e) => {
-                let mut buf = [0u8; 96];
-                {
-                    let mut cur = Cursor::new(&mut buf[..]);
-                    sig.serialize(&mut cur, true)?;
-                }
-                assert_eq!(e.as_ref() as &[u8], &buf[..len]);
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
 
-                let deser = G::deserialize(&mut Cursor::new(&e), true)?;
+                let deser = G::from_compressed(&buf).map_err(_to_io)?;
                 assert_eq!(sig, deser);
             }
         }
@@ -77,9 +82,9 @@ where
 }
 
 /// Test sign functionality for Augmented
-pub fn test_sig_aug<G>(tests: Vec<TestVector>, len: usize) -> Result<()>
+pub fn test_sig_aug<G>(tests: Vec<TestVector>) -> Result<()>
 where
-    G: BLSSignatureAug<ExpandMsgXmd<Sha256>> + CurveProjective + SerDes,
+    G: BLSSignatureAug<ExpandMsgXmd<Sha256>> + PointEncoding,
 {
     for TestVector { msg, sk, expect } in tests {
         let (x_prime, pk) = G::keygen(sk);
@@ -88,14 +93,10 @@ where
         match expect {
             None => println!("{:?}", sig),
             Some(e) => {
-                let mut buf = [0u8; 96];
-                {
-                    let mut cur = Cursor::new(&mut buf[..]);
-                    sig.serialize(&mut cur, true)?;
-                }
-                assert_eq!(e.as_ref() as &[u8], &buf[..len]);
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
 
-                let deser = G::deserialize(&mut Cursor::new(&e), true)?;
+                let deser = G::from_compressed(&buf).map_err(_to_io)?;
                 assert_eq!(sig, deser);
             }
         }
@@ -104,9 +105,9 @@ where
 }
 
 /// Test sign functionality for Pop
-pub fn test_sig_pop<G>(tests: Vec<TestVector>, len: usize) -> Result<()>
+pub fn test_sig_pop<G>(tests: Vec<TestVector>) -> Result<()>
 where
-    G: BLSSignaturePop<ExpandMsgXmd<Sha256>> + CurveProjective + SerDes,
+    G: BLSSignaturePop<ExpandMsgXmd<Sha256>> + PointEncoding,
 {
     for TestVector { msg, sk, expect } in tests {
         let (x_prime, pk) = G::keygen(sk);
@@ -115,14 +116,10 @@ where
         match expect {
             None => println!("{:?}", sig),
             Some(e) => {
-                let mut buf = [0u8; 96];
-                {
-                    let mut cur = Cursor::new(&mut buf[..]);
-                    sig.serialize(&mut cur, true)?;
-                }
-                assert_eq!(e.as_ref() as &[u8], &buf[..len]);
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
 
-                let deser = G::deserialize(&mut Cursor::new(&e), true)?;
+                let deser = G::from_compressed(&buf).map_err(_to_io)?;
                 assert_eq!(sig, deser);
             }
         }
@@ -131,9 +128,9 @@ where
 }
 
 /// Test sign functionality for Pop
-pub fn test_pop<G>(tests: Vec<TestVector>, len: usize) -> Result<()>
+pub fn test_pop<G>(tests: Vec<TestVector>) -> Result<()>
 where
-    G: BLSSignaturePop<ExpandMsgXmd<Sha256>> + CurveProjective + SerDes,
+    G: BLSSignaturePop<ExpandMsgXmd<Sha256>> + PointEncoding,
 {
     for TestVector { sk, expect, .. } in tests {
         let (_, pk) = G::keygen(&sk[..]);
@@ -142,17 +139,163 @@ where
         match expect {
             None => println!("{:?}", sig),
             Some(e) => {
-                let mut buf = [0u8; 96];
-                {
-                    let mut cur = Cursor::new(&mut buf[..]);
-                    sig.serialize(&mut cur, true)?;
-                }
-                assert_eq!(e.as_ref() as &[u8], &buf[..len]);
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
 
-                let deser = G::deserialize(&mut Cursor::new(&e), true)?;
+                let deser = G::from_compressed(&buf).map_err(_to_io)?;
+                assert_eq!(sig, deser);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The number of signers in each BDN test vector
+pub const BDN_SIGNERS: u8 = 3;
+
+/// Test BDN multisignatures; the signers' keys come from sk || I2OSP(i, 1), i < BDN_SIGNERS
+pub fn test_bdn<G>(tests: Vec<TestVector>) -> Result<()>
+where
+    G: BLSSignatureBdn<ExpandMsgXmd<Sha256>> + PointEncoding,
+{
+    for TestVector { msg, sk, expect } in tests {
+        let (pks, sigs): (Vec<_>, Vec<_>) = (0..BDN_SIGNERS)
+            .map(|i| {
+                let mut sk_i = sk.clone();
+                sk_i.push(i);
+                let (x_prime, pk) = G::keygen(sk_i);
+                (
+                    pk,
+                    <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::sign(x_prime, &msg),
+                )
+            })
+            .unzip();
+        let sig = G::bdn_aggregate(&pks, &sigs).map_err(_to_io)?;
+        assert!(<G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::multisig_verify(&pks, sig, &msg));
+        match expect {
+            None => println!("{:?}", sig),
+            Some(e) => {
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
+
+                let deser = G::from_compressed(&buf).map_err(_to_io)?;
+                assert_eq!(sig, deser);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Test the VRF; the expected result is the compressed proof followed by the 32-byte output
+pub fn test_vrf<G>(tests: Vec<TestVector>) -> Result<()>
+where
+    G: BLSVrf<ExpandMsgXmd<Sha256>>,
+{
+    for TestVector { msg, sk, expect } in tests {
+        let (x_prime, pk) = G::keygen(sk);
+        let (beta, proof) = G::vrf_prove(x_prime, &msg);
+        assert_eq!(G::vrf_verify(pk, &msg, proof).map_err(_to_io)?, beta);
+        match expect {
+            None => println!("{:?} {:?}", proof, beta),
+            Some(e) => {
+                let buf = proof.to_compressed();
+                let plen = buf.as_ref().len();
+                assert_eq!(e.len(), plen + 32);
+                assert_eq!(&e[..plen], buf.as_ref());
+                assert_eq!(&e[plen..], &beta[..]);
+
+                let deser = G::from_compressed(&buf).map_err(_to_io)?;
+                assert_eq!(proof, deser);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Test sign functionality for any ciphersuite
+pub fn test_sig_suite<C: Ciphersuite>(tests: Vec<TestVector>) -> Result<()> {
+    for TestVector { msg, sk, expect } in tests {
+        let (x_prime, pk) = C::keygen(sk);
+        let sig = C::sign(x_prime, &msg);
+        assert!(C::verify(pk, sig, &msg));
+        match expect {
+            None => println!("{:?}", sig),
+            Some(e) => {
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
+
+                let deser = SigT::<C>::from_compressed(&buf).map_err(_to_io)?;
+                assert_eq!(sig, deser);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Test proof of possession for any proof-of-possession ciphersuite
+pub fn test_pop_suite<C: PopCiphersuite>(tests: Vec<TestVector>) -> Result<()> {
+    for TestVector { sk, expect, .. } in tests {
+        let (x_prime, pk) = C::keygen(&sk[..]);
+        let sig = C::pop_prove(x_prime);
+        assert!(C::pop_verify(pk, sig));
+        match expect {
+            None => println!("{:?}", sig),
+            Some(e) => {
+                let buf = sig.to_compressed();
+                assert_eq!(e.as_ref() as &[u8], buf.as_ref());
+
+                let deser = SigT::<C>::from_compressed(&buf).map_err(_to_io)?;
                 assert_eq!(sig, deser);
             }
         }
     }
     Ok(())
 }
+
+/// Check that batch verification agrees with verifying each signature separately
+pub fn test_batch_suite<C: Ciphersuite>(tests: Vec<TestVector>) -> Result<()> {
+    let mut pks = Vec::with_capacity(tests.len());
+    let mut msgs = Vec::with_capacity(tests.len());
+    let mut sigs = Vec::with_capacity(tests.len());
+    for TestVector { msg, sk, expect } in tests {
+        let (x_prime, pk) = C::keygen(sk);
+        let sig = C::sign(x_prime, &msg);
+        if let Some(e) = expect {
+            assert_eq!(e.as_ref() as &[u8], sig.to_compressed().as_ref());
+        }
+        assert!(C::verify(pk, sig, &msg));
+        pks.push(pk);
+        msgs.push(msg);
+        sigs.push(sig);
+    }
+    C::try_batch_verify(&pks, &msgs, &sigs).map_err(_to_io)?;
+    assert_eq!(C::batch_find_invalid(&pks, &msgs, &sigs), Ok(vec![]));
+
+    // replacing one signature with another one's must fail in both modes
+    if sigs.len() > 1 {
+        sigs[0] = sigs[1];
+        let valid = C::verify(pks[0], sigs[0], &msgs[0]);
+        assert_eq!(valid, C::batch_verify(&pks, &msgs, &sigs));
+        let expect = if valid { vec![] } else { vec![0] };
+        assert_eq!(C::batch_find_invalid(&pks, &msgs, &sigs), Ok(expect));
+    }
+    Ok(())
+}
+
+/// Test KeyGen; each test vector is (key_info, IKM, SK)
+pub fn test_keygen(tests: Vec<TestVector>, mode: KeyGenMode) -> Result<()> {
+    for TestVector { msg, sk, expect } in tests {
+        let x_prime = key_gen(&sk, &msg, mode).map_err(_to_io)?;
+        match expect {
+            None => println!("{:?}", x_prime),
+            Some(e) => {
+                let buf = sk_to_bytes(&x_prime);
+                assert_eq!(e.as_ref() as &[u8], &buf[..]);
+
+                let deser = sk_from_bytes(&buf).map_err(_to_io)?;
+                assert_eq!(x_prime, deser);
+            }
+        }
+    }
+    Ok(())
+}
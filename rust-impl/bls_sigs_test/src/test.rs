@@ -1,4 +1,12 @@
-use super::{get_dflt_vecs, test_hash, test_pop, test_sig_aug, test_sig_basic, test_sig_pop};
+use super::{
+    get_dflt_vecs, test_batch_suite, test_bdn, test_hash, test_keygen, test_pop, test_pop_suite,
+    test_sig_aug, test_sig_basic, test_sig_pop, test_sig_suite, test_vrf,
+};
+use bls_sigs_ref::{
+    G1XmdSha256Aug, G1XmdSha256Basic, G1XmdSha256Pop, G1XofShake256Aug, G1XofShake256Basic,
+    G1XofShake256Pop, G2XmdSha256Aug, G2XmdSha256Basic, G2XmdSha256Pop, G2XofShake256Aug,
+    G2XofShake256Basic, G2XofShake256Pop, KeyGenMode,
+};
 use pairing_plus::bls12_381::{G1, G2};
 
 #[test]
@@ -15,58 +23,240 @@ fn test_hash_g2() {
     }
 }
 
+#[test]
+fn test_keygen_spec() {
+    for vec in get_dflt_vecs("keygen").unwrap() {
+        test_keygen(vec.unwrap(), KeyGenMode::Spec).unwrap();
+    }
+}
+
+#[test]
+fn test_keygen_legacy() {
+    for vec in get_dflt_vecs("keygen_legacy").unwrap() {
+        test_keygen(vec.unwrap(), KeyGenMode::Legacy).unwrap();
+    }
+}
+
+#[test]
+fn test_bdn_g1() {
+    for vec in get_dflt_vecs("bdn_g1").unwrap() {
+        test_bdn::<G1>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_bdn_g2() {
+    for vec in get_dflt_vecs("bdn_g2").unwrap() {
+        test_bdn::<G2>(vec.unwrap()).unwrap();
+    }
+}
+
 #[test]
 fn test_pop_g1() {
     for vec in get_dflt_vecs("pop_g1").unwrap() {
-        test_pop::<G1>(vec.unwrap(), 48).unwrap();
+        test_pop::<G1>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_pop_g2() {
     for vec in get_dflt_vecs("pop_g2").unwrap() {
-        test_pop::<G2>(vec.unwrap(), 96).unwrap();
+        test_pop::<G2>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_sig_g1_aug() {
     for vec in get_dflt_vecs("sig_g1_aug").unwrap() {
-        test_sig_aug::<G1>(vec.unwrap(), 48).unwrap();
+        test_sig_aug::<G1>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_sig_g1_basic() {
     for vec in get_dflt_vecs("sig_g1_basic").unwrap() {
-        test_sig_basic::<G1>(vec.unwrap(), 48).unwrap();
+        test_sig_basic::<G1>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_sig_g1_pop() {
     for vec in get_dflt_vecs("sig_g1_pop").unwrap() {
-        test_sig_pop::<G1>(vec.unwrap(), 48).unwrap();
+        test_sig_pop::<G1>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_sig_g2_aug() {
     for vec in get_dflt_vecs("sig_g2_aug").unwrap() {
-        test_sig_aug::<G2>(vec.unwrap(), 96).unwrap();
+        test_sig_aug::<G2>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_sig_g2_basic() {
     for vec in get_dflt_vecs("sig_g2_basic").unwrap() {
-        test_sig_basic::<G2>(vec.unwrap(), 96).unwrap();
+        test_sig_basic::<G2>(vec.unwrap()).unwrap();
     }
 }
 
 #[test]
 fn test_sig_g2_pop() {
     for vec in get_dflt_vecs("sig_g2_pop").unwrap() {
-        test_sig_pop::<G2>(vec.unwrap(), 96).unwrap();
+        test_sig_pop::<G2>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_pop_g1_xof() {
+    for vec in get_dflt_vecs("pop_g1_xof").unwrap() {
+        test_pop_suite::<G1XofShake256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_pop_g2_xof() {
+    for vec in get_dflt_vecs("pop_g2_xof").unwrap() {
+        test_pop_suite::<G2XofShake256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_sig_g1_aug_xof() {
+    for vec in get_dflt_vecs("sig_g1_aug_xof").unwrap() {
+        test_sig_suite::<G1XofShake256Aug>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_sig_g1_basic_xof() {
+    for vec in get_dflt_vecs("sig_g1_basic_xof").unwrap() {
+        test_sig_suite::<G1XofShake256Basic>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_sig_g1_pop_xof() {
+    for vec in get_dflt_vecs("sig_g1_pop_xof").unwrap() {
+        test_sig_suite::<G1XofShake256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_sig_g2_aug_xof() {
+    for vec in get_dflt_vecs("sig_g2_aug_xof").unwrap() {
+        test_sig_suite::<G2XofShake256Aug>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_sig_g2_basic_xof() {
+    for vec in get_dflt_vecs("sig_g2_basic_xof").unwrap() {
+        test_sig_suite::<G2XofShake256Basic>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_sig_g2_pop_xof() {
+    for vec in get_dflt_vecs("sig_g2_pop_xof").unwrap() {
+        test_sig_suite::<G2XofShake256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g1_aug() {
+    for vec in get_dflt_vecs("sig_g1_aug").unwrap() {
+        test_batch_suite::<G1XmdSha256Aug>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g1_aug_xof() {
+    for vec in get_dflt_vecs("sig_g1_aug_xof").unwrap() {
+        test_batch_suite::<G1XofShake256Aug>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g1_basic() {
+    for vec in get_dflt_vecs("sig_g1_basic").unwrap() {
+        test_batch_suite::<G1XmdSha256Basic>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g1_basic_xof() {
+    for vec in get_dflt_vecs("sig_g1_basic_xof").unwrap() {
+        test_batch_suite::<G1XofShake256Basic>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g1_pop() {
+    for vec in get_dflt_vecs("sig_g1_pop").unwrap() {
+        test_batch_suite::<G1XmdSha256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g1_pop_xof() {
+    for vec in get_dflt_vecs("sig_g1_pop_xof").unwrap() {
+        test_batch_suite::<G1XofShake256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g2_aug() {
+    for vec in get_dflt_vecs("sig_g2_aug").unwrap() {
+        test_batch_suite::<G2XmdSha256Aug>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g2_aug_xof() {
+    for vec in get_dflt_vecs("sig_g2_aug_xof").unwrap() {
+        test_batch_suite::<G2XofShake256Aug>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g2_basic() {
+    for vec in get_dflt_vecs("sig_g2_basic").unwrap() {
+        test_batch_suite::<G2XmdSha256Basic>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g2_basic_xof() {
+    for vec in get_dflt_vecs("sig_g2_basic_xof").unwrap() {
+        test_batch_suite::<G2XofShake256Basic>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g2_pop() {
+    for vec in get_dflt_vecs("sig_g2_pop").unwrap() {
+        test_batch_suite::<G2XmdSha256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_g2_pop_xof() {
+    for vec in get_dflt_vecs("sig_g2_pop_xof").unwrap() {
+        test_batch_suite::<G2XofShake256Pop>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_vrf_g1() {
+    for vec in get_dflt_vecs("vrf_g1").unwrap() {
+        test_vrf::<G1>(vec.unwrap()).unwrap();
+    }
+}
+
+#[test]
+fn test_vrf_g2() {
+    for vec in get_dflt_vecs("vrf_g2").unwrap() {
+        test_vrf::<G2>(vec.unwrap()).unwrap();
     }
 }
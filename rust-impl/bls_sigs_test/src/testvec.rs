@@ -1,6 +1,6 @@
 use std::env::{args, var};
 use std::fs::{read_dir, File};
-use std::io::{BufRead, BufReader, Error, ErrorKind, Result};
+use std::io::{BufRead, BufReader, Error, Result};
 use std::path::PathBuf;
 
 fn hexnum(c: u8) -> u8 {
@@ -15,7 +15,7 @@ fn hexnum(c: u8) -> u8 {
 // ASCII hexstring to bytes
 fn hexstring_to_bytes(input: &str) -> Vec<u8> {
     let input = input.as_bytes();
-    assert!(input.len() % 2 == 0);
+    assert!(input.len().is_multiple_of(2));
     let ret_len = input.len() / 2;
     let mut ret = Vec::<u8>::with_capacity(ret_len);
 
@@ -41,7 +41,7 @@ fn proc_testvec_line(input: &str) -> TestVector {
     let mut result: Vec<Vec<u8>> = input
         .split_ascii_whitespace()
         .take(3)
-        .map(|s| hexstring_to_bytes(s))
+        .map(hexstring_to_bytes)
         .collect();
     let expect = if result.len() > 2 { result.pop() } else { None };
     let sk = result.pop().unwrap();
@@ -80,8 +80,7 @@ pub fn get_dflt_vecs(test_type: &str) -> Result<Box<dyn Iterator<Item = Result<V
             proc_testvec_file(d.unwrap().path().to_str().unwrap())
         })))
     } else {
-        Err(Error::new(
-            ErrorKind::Other,
+        Err(Error::other(
             "No cmdline arguments and std test vectors not found",
         ))
     }
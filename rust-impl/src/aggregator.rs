@@ -0,0 +1,117 @@
+/*!
+Incremental aggregation of signatures or public keys
+
+`BLSSigCore::aggregate` sums a complete slice of signatures. An `Aggregator` instead
+keeps a running sum and a count of members, so that signatures (or public keys) that
+arrive over time can be added, removed, and merged as they come in. Its state is just
+the count and the sum, so it encodes compactly and can be persisted and resumed.
+*/
+
+use encoding::PointEncoding;
+use error::BlsError;
+use pairing_plus::CurveProjective;
+use std::convert::TryFrom;
+
+/// A running sum of points in `G`, with a count of how many points went into it.
+///
+/// The aggregator does not remember which points it holds: `remove` subtracts whatever
+/// point it is given, and `add` accepts duplicates. Callers that need those guarantees
+/// should track membership themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aggregator<G: CurveProjective> {
+    sum: G,
+    count: usize,
+}
+
+impl<G: CurveProjective> Aggregator<G> {
+    /// An empty aggregator
+    pub fn new() -> Self {
+        Aggregator {
+            sum: G::zero(),
+            count: 0,
+        }
+    }
+
+    /// An aggregator holding each of `points`
+    pub fn from_points(points: &[G]) -> Self {
+        let mut ret = Self::new();
+        for pt in points {
+            ret.add(pt);
+        }
+        ret
+    }
+
+    /// Add a point
+    pub fn add(&mut self, point: &G) {
+        self.sum.add_assign(point);
+        self.count += 1;
+    }
+
+    /// Remove a point that was previously added. Fails if the aggregator is empty.
+    pub fn remove(&mut self, point: &G) -> Result<(), BlsError> {
+        if self.count == 0 {
+            return Err(BlsError::EmptyInput);
+        }
+        self.sum.sub_assign(point);
+        self.count -= 1;
+        Ok(())
+    }
+
+    /// Add every point held by `other`
+    pub fn merge(&mut self, other: &Self) {
+        self.sum.add_assign(&other.sum);
+        self.count += other.count;
+    }
+
+    /// The number of points added, less the number removed
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether the aggregator holds no points
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The sum of the points held
+    pub fn as_point(&self) -> &G {
+        &self.sum
+    }
+}
+
+impl<G: CurveProjective> Default for Aggregator<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: PointEncoding> Aggregator<G> {
+    /// Serialize as the count, an 8-byte big-endian integer, followed by the compressed sum:
+    /// 56 bytes in G1, 104 bytes in G2
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let sum = self.sum.to_compressed();
+        let mut ret = Vec::with_capacity(8 + sum.as_ref().len());
+        ret.extend_from_slice(&(self.count as u64).to_be_bytes());
+        ret.extend_from_slice(sum.as_ref());
+        ret
+    }
+
+    /// Deserialize the output of `to_bytes`. Fails if the length is wrong, if the sum does
+    /// not decode, or if the count is zero but the sum is not the identity.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let mut enc = G::zero().to_compressed();
+        if bytes.len() != 8 + enc.as_ref().len() {
+            return Err(BlsError::InvalidEncoding);
+        }
+        let mut count = [0u8; 8];
+        count.copy_from_slice(&bytes[..8]);
+        let count =
+            usize::try_from(u64::from_be_bytes(count)).map_err(|_| BlsError::InvalidEncoding)?;
+        enc.as_mut().copy_from_slice(&bytes[8..]);
+        let sum = G::from_compressed(&enc)?;
+        if count == 0 && !sum.is_zero() {
+            return Err(BlsError::InvalidEncoding);
+        }
+        Ok(Aggregator { sum, count })
+    }
+}
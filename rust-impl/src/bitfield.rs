@@ -0,0 +1,201 @@
+/*!
+Aggregate signatures that name their signers with a bitmap
+
+Instead of a list of public keys, a certificate for a committee vote can carry one bit
+per committee member. A `BitfieldAggregate` is an aggregate signature on one message
+together with such a bitmap over a `Committee`; the verifier rebuilds the aggregate
+public key from the committee and the bitmap.
+*/
+
+use ciphersuite::{PopCiphersuite, SchemeKind, SigT};
+use committee::{_get_bit, Committee};
+use encoding::PointEncoding;
+use error::BlsError;
+use pairing_plus::CurveProjective;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// An aggregate signature on one message by the members of a committee of size `len()`
+/// whose bits are set.
+///
+/// Bit i of the bitmap is bit `i % 8` (least significant first) of byte `i / 8`.
+///
+/// The signature is held as a projective point rather than in compressed form, so that
+/// `add` and `merge` are a single group addition instead of a decompression, addition,
+/// and recompression each. `to_bytes` compresses it, and `from_bytes` checks it.
+pub struct BitfieldAggregate<C: PopCiphersuite> {
+    sig: SigT<C>,
+    bits: Vec<u8>,
+    len: usize,
+}
+
+impl<C: PopCiphersuite> BitfieldAggregate<C> {
+    /// An empty aggregate for a committee of `len` members.
+    /// Fails if `len` does not fit in the 4-byte size field of `to_bytes`.
+    pub fn new(len: usize) -> Result<Self, BlsError> {
+        if len > u32::MAX as usize {
+            return Err(BlsError::CommitteeTooLarge);
+        }
+        Ok(BitfieldAggregate {
+            sig: SigT::<C>::zero(),
+            bits: vec![0u8; len.div_ceil(8)],
+            len,
+        })
+    }
+
+    /// Add the signature of committee member `index`.
+    /// Fails if `index` is out of range or that member's bit is already set.
+    pub fn add(&mut self, index: usize, sig: &SigT<C>) -> Result<(), BlsError> {
+        if index >= self.len {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        if _get_bit(&self.bits[..], index) {
+            return Err(BlsError::DuplicateSigner);
+        }
+        self.bits[index / 8] |= 1 << (index % 8);
+        self.sig.add_assign(sig);
+        Ok(())
+    }
+
+    /// Add every signature held by `other`. Fails if the two are for committees of
+    /// different sizes or if any member's bit is set in both.
+    pub fn merge(&mut self, other: &Self) -> Result<(), BlsError> {
+        if self.len != other.len {
+            return Err(BlsError::LengthMismatch);
+        }
+        if self.bits.iter().zip(&other.bits).any(|(a, b)| a & b != 0) {
+            return Err(BlsError::DuplicateSigner);
+        }
+        for (a, b) in self.bits.iter_mut().zip(&other.bits) {
+            *a |= b;
+        }
+        self.sig.add_assign(&other.sig);
+        Ok(())
+    }
+
+    /// The size of the committee
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the committee is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether member `index` has signed
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.len && _get_bit(&self.bits[..], index)
+    }
+
+    /// The number of members who have signed
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// The indices of the members who have signed, in increasing order
+    pub fn signers(&self) -> Vec<usize> {
+        (0..self.len)
+            .filter(|&i| _get_bit(&self.bits[..], i))
+            .collect()
+    }
+
+    /// The aggregate signature
+    pub fn as_signature(&self) -> &SigT<C> {
+        &self.sig
+    }
+
+    /// The bitmap of signers
+    pub fn as_bits(&self) -> &[u8] {
+        &self.bits[..]
+    }
+
+    /// Verify this aggregate on `msg` against `committee`
+    pub fn verify<B: AsRef<[u8]>>(&self, committee: &Committee<C>, msg: B) -> bool {
+        self.try_verify(committee, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        committee: &Committee<C>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        if committee.len() != self.len {
+            return Err(BlsError::LengthMismatch);
+        }
+        if self.bits.iter().all(|&b| b == 0) {
+            return Err(BlsError::EmptyInput);
+        }
+        let apk = committee.signer_apk(&self.bits[..]);
+        if apk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        C::Scheme::try_verify_prevalidated::<C, B>(apk, self.sig, msg)
+    }
+
+    /// Serialize as the committee size, a 4-byte big-endian integer, followed by the
+    /// compressed signature and then the bitmap. `new` ensures the size fits in 4 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let sig = self.sig.to_compressed();
+        let mut ret = Vec::with_capacity(4 + sig.as_ref().len() + self.bits.len());
+        ret.extend_from_slice(&(self.len as u32).to_be_bytes());
+        ret.extend_from_slice(sig.as_ref());
+        ret.extend_from_slice(&self.bits[..]);
+        ret
+    }
+
+    /// Deserialize the output of `to_bytes`. Fails if the length is wrong, if the
+    /// signature does not decode, or if any bit past the end of the committee is set.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        if bytes.len() < 4 {
+            return Err(BlsError::InvalidEncoding);
+        }
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&bytes[..4]);
+        let len =
+            usize::try_from(u32::from_be_bytes(len)).map_err(|_| BlsError::InvalidEncoding)?;
+        let mut enc = SigT::<C>::zero().to_compressed();
+        let sig_len = enc.as_ref().len();
+        if bytes.len() != 4 + sig_len + len.div_ceil(8) {
+            return Err(BlsError::InvalidEncoding);
+        }
+        enc.as_mut().copy_from_slice(&bytes[4..4 + sig_len]);
+        let bits = bytes[4 + sig_len..].to_vec();
+        if !len.is_multiple_of(8) && bits[len / 8] >> (len % 8) != 0 {
+            return Err(BlsError::InvalidEncoding);
+        }
+        Ok(BitfieldAggregate {
+            sig: SigT::<C>::from_compressed(&enc)?,
+            bits,
+            len,
+        })
+    }
+}
+
+impl<C: PopCiphersuite> Clone for BitfieldAggregate<C> {
+    fn clone(&self) -> Self {
+        BitfieldAggregate {
+            sig: self.sig,
+            bits: self.bits.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<C: PopCiphersuite> PartialEq for BitfieldAggregate<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.bits == other.bits && self.sig == other.sig
+    }
+}
+
+impl<C: PopCiphersuite> Eq for BitfieldAggregate<C> {}
+
+impl<C: PopCiphersuite> fmt::Debug for BitfieldAggregate<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitfieldAggregate")
+            .field("sig", &self.sig)
+            .field("signers", &self.signers())
+            .finish()
+    }
+}
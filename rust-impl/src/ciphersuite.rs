@@ -0,0 +1,1130 @@
+/*!
+Ciphersuites: a group, an expand_message variant, a scheme, and a domain separation tag
+
+The `BLSSignatureBasic`, `BLSSignatureAug`, and `BLSSignaturePop` traits fix one tag per
+group and expand_message variant. A `Ciphersuite` instead bundles all four choices into
+one type, so applications can declare their own tags and hash functions with the
+`ciphersuite!` macro. The six standard suites from the draft are declared below, along
+with the six corresponding suites that use expand_message_xof with SHAKE-256.
+*/
+
+use aggregator::Aggregator;
+use encoding::PointEncoding;
+use error::BlsError;
+use hashed::HashedMessage;
+use pairing_plus::bls12_381::{G1, G2};
+use pairing_plus::hash_to_field::{ExpandMsg, ExpandMsgXmd, ExpandMsgXof};
+use pairing_plus::serdes::SerDes;
+use pairing_plus::{CurveAffine, CurveProjective};
+use prepared::PreparedPublicKey;
+use rand::thread_rng;
+use sha2::Sha256;
+use sha3::Shake256;
+use signature::{
+    _agg_help, _batch_len_check, _len_check, _unique_help, _weighted_apk, BLSSigCore,
+    BLSSignatureAug, BLSSignatureBasic, BLSSignaturePop,
+};
+use std::borrow::Borrow;
+use typed::{Aug, Basic, Pop};
+use zeroize::Zeroize;
+
+/// Alias for the scalar type corresponding to a CurveProjective type
+type ScalarT<PtT> = <PtT as CurveProjective>::Scalar;
+
+/// Alias for the signature group of a ciphersuite
+pub type SigT<C> = <C as Ciphersuite>::Group;
+
+/// Alias for the public key group of a ciphersuite
+pub type PkT<C> = <C as Ciphersuite>::PkGroup;
+
+/// The parts of signing and verifying that depend on the scheme: implemented by the
+/// `Basic`, `Aug`, and `Pop` marker types
+pub trait SchemeKind: 'static {
+    /// Sign a message
+    fn try_sign<C, B>(x_prime: ScalarT<SigT<C>>, msg: B) -> Result<SigT<C>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+
+    /// Verify a signature, without running `key_validate` on the public key
+    fn try_verify_prevalidated<C, B>(pk: PkT<C>, sig: SigT<C>, msg: B) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+
+    /// Verify an aggregate signature, without running `key_validate` on the public keys
+    fn try_aggregate_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+
+    /// Verify an aggregate signature on messages that were already hashed, without
+    /// running `key_validate` on the public keys
+    fn try_aggregate_verify_hashed_prevalidated<C>(
+        pks: &[PkT<C>],
+        hashed: &[&HashedMessage<SigT<C>, C>],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>;
+
+    /// Verify a signature under a prepared public key
+    fn try_verify_prepared<C, B>(
+        pk: &PreparedPublicKey<SigT<C>, C>,
+        sig: SigT<C>,
+        msg: B,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+
+    /// Verify an aggregate signature under prepared public keys
+    fn try_aggregate_verify_prepared<C, B>(
+        pks: &[&PreparedPublicKey<SigT<C>, C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+
+    /// Verify independent (pk, msg, sig) triples as a batch, without running
+    /// `key_validate` on the public keys
+    fn try_batch_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+
+    /// Find the invalid entries among independent (pk, msg, sig) triples, without
+    /// running `key_validate` on the public keys
+    fn batch_find_invalid_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<Vec<usize>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>;
+}
+
+// aggregate verification on hashed messages, shared by all three schemes
+fn _agg_verify_hashed_help<C: Ciphersuite>(
+    pks: &[PkT<C>],
+    hashed: &[&HashedMessage<SigT<C>, C>],
+    sig: SigT<C>,
+) -> Result<(), BlsError> {
+    let prepared: Vec<_> = hashed.iter().map(|h| h.as_prepared()).collect();
+    <SigT<C> as BLSSigCore<C::Expand>>::try_core_aggregate_verify_hashed_prevalidated(
+        pks,
+        &prepared[..],
+        sig,
+    )
+}
+
+// verification under a prepared public key, shared by all three schemes
+fn _verify_prepared_help<C: Ciphersuite, B: AsRef<[u8]>>(
+    pk: &PreparedPublicKey<SigT<C>, C>,
+    sig: SigT<C>,
+    msg: B,
+) -> Result<(), BlsError> {
+    let hashed = <SigT<C> as BLSSigCore<C::Expand>>::hash_to_point(msg, C::DST)
+        .into_affine()
+        .prepare();
+    <SigT<C> as BLSSigCore<C::Expand>>::try_core_verify_prepared(pk.as_prepared(), sig, &hashed)
+}
+
+// aggregate verification under prepared public keys, shared by all three schemes
+fn _agg_verify_prepared_help<C: Ciphersuite, B: AsRef<[u8]>>(
+    pks: &[&PreparedPublicKey<SigT<C>, C>],
+    msgs: &[B],
+    sig: SigT<C>,
+) -> Result<(), BlsError> {
+    let prepared: Vec<_> = pks.iter().map(|pk| pk.as_prepared()).collect();
+    <SigT<C> as BLSSigCore<C::Expand>>::try_core_aggregate_verify_prepared(
+        &prepared[..],
+        msgs,
+        sig,
+        C::DST,
+    )
+}
+
+impl SchemeKind for Basic {
+    fn try_sign<C, B>(x_prime: ScalarT<SigT<C>>, msg: B) -> Result<SigT<C>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        Ok(<SigT<C> as BLSSigCore<C::Expand>>::core_sign(
+            x_prime,
+            msg,
+            C::DST,
+        ))
+    }
+
+    fn try_verify_prevalidated<C, B>(pk: PkT<C>, sig: SigT<C>, msg: B) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_verify_prevalidated(pk, sig, msg, C::DST)
+    }
+
+    fn try_aggregate_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _len_check(pks.len(), msgs.len())?;
+        _unique_help(msgs)?;
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_aggregate_verify_prevalidated(
+            pks,
+            msgs,
+            sig,
+            C::DST,
+        )
+    }
+
+    fn try_aggregate_verify_hashed_prevalidated<C>(
+        pks: &[PkT<C>],
+        hashed: &[&HashedMessage<SigT<C>, C>],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+    {
+        _len_check(pks.len(), hashed.len())?;
+        // distinct messages hash to distinct points, and equal messages to equal points
+        let points: Vec<_> = hashed
+            .iter()
+            .map(|h| h.as_point().to_compressed())
+            .collect();
+        _unique_help(&points[..])?;
+        _agg_verify_hashed_help(pks, hashed, sig)
+    }
+
+    fn try_verify_prepared<C, B>(
+        pk: &PreparedPublicKey<SigT<C>, C>,
+        sig: SigT<C>,
+        msg: B,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _verify_prepared_help(pk, sig, msg)
+    }
+
+    fn try_aggregate_verify_prepared<C, B>(
+        pks: &[&PreparedPublicKey<SigT<C>, C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _len_check(pks.len(), msgs.len())?;
+        _unique_help(msgs)?;
+        _agg_verify_prepared_help(pks, msgs, sig)
+    }
+
+    fn try_batch_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_batch_verify_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            C::DST,
+        )
+    }
+
+    fn batch_find_invalid_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<Vec<usize>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            C::DST,
+        )
+    }
+}
+
+// PK || msg, with PK in compressed form
+pub(crate) fn _augment<P: PointEncoding, B: AsRef<[u8]>>(pk: &P, msg: B) -> Vec<u8> {
+    let pk_bytes = pk.to_compressed();
+    let mut ret = Vec::with_capacity(pk_bytes.as_ref().len() + msg.as_ref().len());
+    ret.extend_from_slice(pk_bytes.as_ref());
+    ret.extend_from_slice(msg.as_ref());
+    ret
+}
+
+impl SchemeKind for Aug {
+    fn try_sign<C, B>(mut x_prime: ScalarT<SigT<C>>, msg: B) -> Result<SigT<C>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        let mut pk = PkT::<C>::one();
+        pk.mul_assign(x_prime);
+        let ret =
+            <SigT<C> as BLSSigCore<C::Expand>>::core_sign(x_prime, _augment(&pk, msg), C::DST);
+        x_prime.zeroize();
+        Ok(ret)
+    }
+
+    fn try_verify_prevalidated<C, B>(pk: PkT<C>, sig: SigT<C>, msg: B) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_verify_prevalidated(
+            pk,
+            sig,
+            _augment(&pk, msg),
+            C::DST,
+        )
+    }
+
+    fn try_aggregate_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _len_check(pks.len(), msgs.len())?;
+        let pks_msgs: Vec<_> = pks
+            .iter()
+            .zip(msgs)
+            .map(|(pk, msg)| _augment(pk, msg))
+            .collect();
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_aggregate_verify_prevalidated(
+            pks,
+            &pks_msgs[..],
+            sig,
+            C::DST,
+        )
+    }
+
+    fn try_aggregate_verify_hashed_prevalidated<C>(
+        pks: &[PkT<C>],
+        hashed: &[&HashedMessage<SigT<C>, C>],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+    {
+        _agg_verify_hashed_help(pks, hashed, sig)
+    }
+
+    fn try_verify_prepared<C, B>(
+        pk: &PreparedPublicKey<SigT<C>, C>,
+        sig: SigT<C>,
+        msg: B,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _verify_prepared_help(pk, sig, _augment(pk.as_point(), msg))
+    }
+
+    fn try_aggregate_verify_prepared<C, B>(
+        pks: &[&PreparedPublicKey<SigT<C>, C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _len_check(pks.len(), msgs.len())?;
+        let pks_msgs: Vec<_> = pks
+            .iter()
+            .zip(msgs)
+            .map(|(pk, msg)| _augment(pk.as_point(), msg))
+            .collect();
+        _agg_verify_prepared_help(pks, &pks_msgs[..], sig)
+    }
+
+    fn try_batch_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let pks_msgs: Vec<_> = pks
+            .iter()
+            .zip(msgs)
+            .map(|(pk, msg)| _augment(pk, msg))
+            .collect();
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_batch_verify_prevalidated(
+            &mut thread_rng(),
+            pks,
+            &pks_msgs[..],
+            sigs,
+            C::DST,
+        )
+    }
+
+    fn batch_find_invalid_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<Vec<usize>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let pks_msgs: Vec<_> = pks
+            .iter()
+            .zip(msgs)
+            .map(|(pk, msg)| _augment(pk, msg))
+            .collect();
+        <SigT<C> as BLSSigCore<C::Expand>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            pks,
+            &pks_msgs[..],
+            sigs,
+            C::DST,
+        )
+    }
+}
+
+impl SchemeKind for Pop {
+    fn try_sign<C, B>(x_prime: ScalarT<SigT<C>>, msg: B) -> Result<SigT<C>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        Ok(<SigT<C> as BLSSigCore<C::Expand>>::core_sign(
+            x_prime,
+            msg,
+            C::DST,
+        ))
+    }
+
+    fn try_verify_prevalidated<C, B>(pk: PkT<C>, sig: SigT<C>, msg: B) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_verify_prevalidated(pk, sig, msg, C::DST)
+    }
+
+    fn try_aggregate_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_aggregate_verify_prevalidated(
+            pks,
+            msgs,
+            sig,
+            C::DST,
+        )
+    }
+
+    fn try_aggregate_verify_hashed_prevalidated<C>(
+        pks: &[PkT<C>],
+        hashed: &[&HashedMessage<SigT<C>, C>],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+    {
+        _agg_verify_hashed_help(pks, hashed, sig)
+    }
+
+    fn try_verify_prepared<C, B>(
+        pk: &PreparedPublicKey<SigT<C>, C>,
+        sig: SigT<C>,
+        msg: B,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _verify_prepared_help(pk, sig, msg)
+    }
+
+    fn try_aggregate_verify_prepared<C, B>(
+        pks: &[&PreparedPublicKey<SigT<C>, C>],
+        msgs: &[B],
+        sig: SigT<C>,
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        _agg_verify_prepared_help(pks, msgs, sig)
+    }
+
+    fn try_batch_verify_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<(), BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::try_core_batch_verify_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            C::DST,
+        )
+    }
+
+    fn batch_find_invalid_prevalidated<C, B>(
+        pks: &[PkT<C>],
+        msgs: &[B],
+        sigs: &[SigT<C>],
+    ) -> Result<Vec<usize>, BlsError>
+    where
+        C: Ciphersuite<Scheme = Self>,
+        B: AsRef<[u8]>,
+    {
+        <SigT<C> as BLSSigCore<C::Expand>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            C::DST,
+        )
+    }
+}
+
+/// A BLS ciphersuite. Declare new ones with the `ciphersuite!` macro.
+pub trait Ciphersuite: Sized + 'static {
+    /// The expand_message variant used to hash messages to the curve
+    type Expand: ExpandMsg;
+
+    /// The group in which signatures live
+    type Group: BLSSigCore<Self::Expand, PKType = Self::PkGroup> + PointEncoding;
+
+    /// The group in which public keys live: the other one
+    type PkGroup: CurveProjective<
+            Engine = <Self::Group as CurveProjective>::Engine,
+            Scalar = ScalarT<Self::Group>,
+        > + PointEncoding
+        + SerDes;
+
+    /// The scheme: `Basic`, `Aug`, or `Pop`
+    type Scheme: SchemeKind;
+
+    /// Domain separation tag for hashing messages
+    const DST: &'static [u8];
+
+    /// Generate secret exponent and public key from the secret key bytes
+    fn keygen<B: AsRef<[u8]>>(sk: B) -> (ScalarT<SigT<Self>>, PkT<Self>) {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::keygen(sk)
+    }
+
+    /// Like `keygen`, but returns an error instead of panicking
+    fn try_keygen<B: AsRef<[u8]>>(sk: B) -> Result<(ScalarT<SigT<Self>>, PkT<Self>), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::try_keygen(sk)
+    }
+
+    /// Check that a public key is not the identity and is in the prime-order subgroup
+    fn key_validate(pk: &PkT<Self>) -> Result<(), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)
+    }
+
+    /// Sign a message
+    fn sign<B: AsRef<[u8]>>(x_prime: ScalarT<SigT<Self>>, msg: B) -> SigT<Self> {
+        // serializing a public key cannot fail
+        Self::try_sign(x_prime, msg).unwrap()
+    }
+
+    /// Like `sign`, but returns an error instead of panicking
+    fn try_sign<B: AsRef<[u8]>>(
+        x_prime: ScalarT<SigT<Self>>,
+        msg: B,
+    ) -> Result<SigT<Self>, BlsError> {
+        Self::Scheme::try_sign::<Self, B>(x_prime, msg)
+    }
+
+    /// Verify a signature
+    fn verify<B: AsRef<[u8]>>(pk: PkT<Self>, sig: SigT<Self>, msg: B) -> bool {
+        Self::try_verify(pk, sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    fn try_verify<B: AsRef<[u8]>>(pk: PkT<Self>, sig: SigT<Self>, msg: B) -> Result<(), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(&pk)?;
+        Self::try_verify_prevalidated(pk, sig, msg)
+    }
+
+    /// Like `try_verify`, but skips `key_validate` on the public key
+    fn try_verify_prevalidated<B: AsRef<[u8]>>(
+        pk: PkT<Self>,
+        sig: SigT<Self>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        Self::Scheme::try_verify_prevalidated::<Self, B>(pk, sig, msg)
+    }
+
+    /// Sign a message that was already hashed. Under `Aug`, the message must have been
+    /// hashed together with the signer's own public key.
+    fn sign_hashed(
+        x_prime: ScalarT<SigT<Self>>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> SigT<Self> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::core_sign_hashed(x_prime, hashed.as_point())
+    }
+
+    /// Verify a signature on a message that was already hashed
+    fn verify_hashed(
+        pk: PkT<Self>,
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> bool {
+        Self::try_verify_hashed(pk, sig, hashed).is_ok()
+    }
+
+    /// Like `verify_hashed`, but reports why verification failed
+    fn try_verify_hashed(
+        pk: PkT<Self>,
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> Result<(), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(&pk)?;
+        Self::try_verify_hashed_prevalidated(pk, sig, hashed)
+    }
+
+    /// Like `try_verify_hashed`, but skips `key_validate` on the public key
+    fn try_verify_hashed_prevalidated(
+        pk: PkT<Self>,
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> Result<(), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::try_core_verify_hashed_prevalidated(
+            pk,
+            sig,
+            hashed.as_prepared(),
+        )
+    }
+
+    /// Verify a signature under a prepared public key. The key was validated when it was
+    /// prepared, so there is no separate `_prevalidated` variant.
+    fn verify_prepared<B: AsRef<[u8]>>(
+        pk: &PreparedPublicKey<SigT<Self>, Self>,
+        sig: SigT<Self>,
+        msg: B,
+    ) -> bool {
+        Self::try_verify_prepared(pk, sig, msg).is_ok()
+    }
+
+    /// Like `verify_prepared`, but reports why verification failed
+    fn try_verify_prepared<B: AsRef<[u8]>>(
+        pk: &PreparedPublicKey<SigT<Self>, Self>,
+        sig: SigT<Self>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        Self::Scheme::try_verify_prepared::<Self, B>(pk, sig, msg)
+    }
+
+    /// Verify a signature on a message that was already hashed, under a prepared public key
+    fn verify_hashed_prepared(
+        pk: &PreparedPublicKey<SigT<Self>, Self>,
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> bool {
+        Self::try_verify_hashed_prepared(pk, sig, hashed).is_ok()
+    }
+
+    /// Like `verify_hashed_prepared`, but reports why verification failed
+    fn try_verify_hashed_prepared(
+        pk: &PreparedPublicKey<SigT<Self>, Self>,
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> Result<(), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::try_core_verify_prepared(
+            pk.as_prepared(),
+            sig,
+            hashed.as_prepared(),
+        )
+    }
+
+    /// Verify an aggregate signature
+    fn aggregate_verify<B: AsRef<[u8]>>(pks: &[PkT<Self>], msgs: &[B], sig: SigT<Self>) -> bool {
+        Self::try_aggregate_verify(pks, msgs, sig).is_ok()
+    }
+
+    /// Like `aggregate_verify`, but reports why verification failed
+    fn try_aggregate_verify<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        msgs: &[B],
+        sig: SigT<Self>,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        for pk in pks {
+            <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)?;
+        }
+        Self::try_aggregate_verify_prevalidated(pks, msgs, sig)
+    }
+
+    /// Like `try_aggregate_verify`, but skips `key_validate` on the public keys
+    fn try_aggregate_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        msgs: &[B],
+        sig: SigT<Self>,
+    ) -> Result<(), BlsError> {
+        Self::Scheme::try_aggregate_verify_prevalidated::<Self, B>(pks, msgs, sig)
+    }
+
+    /// Verify an aggregate signature on messages that were already hashed
+    fn aggregate_verify_hashed<H: Borrow<HashedMessage<SigT<Self>, Self>>>(
+        pks: &[PkT<Self>],
+        hashed: &[H],
+        sig: SigT<Self>,
+    ) -> bool {
+        Self::try_aggregate_verify_hashed(pks, hashed, sig).is_ok()
+    }
+
+    /// Like `aggregate_verify_hashed`, but reports why verification failed
+    fn try_aggregate_verify_hashed<H: Borrow<HashedMessage<SigT<Self>, Self>>>(
+        pks: &[PkT<Self>],
+        hashed: &[H],
+        sig: SigT<Self>,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), hashed.len())?;
+        for pk in pks {
+            <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)?;
+        }
+        Self::try_aggregate_verify_hashed_prevalidated(pks, hashed, sig)
+    }
+
+    /// Like `try_aggregate_verify_hashed`, but skips `key_validate` on the public keys
+    fn try_aggregate_verify_hashed_prevalidated<H: Borrow<HashedMessage<SigT<Self>, Self>>>(
+        pks: &[PkT<Self>],
+        hashed: &[H],
+        sig: SigT<Self>,
+    ) -> Result<(), BlsError> {
+        let hashed: Vec<_> = hashed.iter().map(|h| h.borrow()).collect();
+        Self::Scheme::try_aggregate_verify_hashed_prevalidated::<Self>(pks, &hashed[..], sig)
+    }
+
+    /// Verify an aggregate signature under prepared public keys
+    fn aggregate_verify_prepared<P, B>(pks: &[P], msgs: &[B], sig: SigT<Self>) -> bool
+    where
+        P: Borrow<PreparedPublicKey<SigT<Self>, Self>>,
+        B: AsRef<[u8]>,
+    {
+        Self::try_aggregate_verify_prepared(pks, msgs, sig).is_ok()
+    }
+
+    /// Like `aggregate_verify_prepared`, but reports why verification failed
+    fn try_aggregate_verify_prepared<P, B>(
+        pks: &[P],
+        msgs: &[B],
+        sig: SigT<Self>,
+    ) -> Result<(), BlsError>
+    where
+        P: Borrow<PreparedPublicKey<SigT<Self>, Self>>,
+        B: AsRef<[u8]>,
+    {
+        let pks: Vec<_> = pks.iter().map(|pk| pk.borrow()).collect();
+        Self::Scheme::try_aggregate_verify_prepared::<Self, B>(&pks[..], msgs, sig)
+    }
+
+    /// Verify independent (pk, msg, sig) triples as a batch, using one multi-Miller loop
+    /// and one final exponentiation; see `BLSSigCore::core_batch_verify`
+    fn batch_verify<B: AsRef<[u8]>>(pks: &[PkT<Self>], msgs: &[B], sigs: &[SigT<Self>]) -> bool {
+        Self::try_batch_verify(pks, msgs, sigs).is_ok()
+    }
+
+    /// Like `batch_verify`, but reports why verification failed
+    fn try_batch_verify<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        msgs: &[B],
+        sigs: &[SigT<Self>],
+    ) -> Result<(), BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        for pk in pks {
+            <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)?;
+        }
+        Self::try_batch_verify_prevalidated(pks, msgs, sigs)
+    }
+
+    /// Like `try_batch_verify`, but skips `key_validate` on the public keys
+    fn try_batch_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        msgs: &[B],
+        sigs: &[SigT<Self>],
+    ) -> Result<(), BlsError> {
+        Self::Scheme::try_batch_verify_prevalidated::<Self, B>(pks, msgs, sigs)
+    }
+
+    /// Find the invalid entries among independent (pk, msg, sig) triples, returning their
+    /// indices in increasing order; see `BLSSigCore::core_batch_find_invalid`
+    fn batch_find_invalid<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        msgs: &[B],
+        sigs: &[SigT<Self>],
+    ) -> Result<Vec<usize>, BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let mut bad = Vec::new();
+        let mut good = Vec::with_capacity(pks.len());
+        for (i, pk) in pks.iter().enumerate() {
+            if <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk).is_ok() {
+                good.push(i);
+            } else {
+                bad.push(i);
+            }
+        }
+        if good.is_empty() {
+            return Ok(bad);
+        }
+        let good_pks: Vec<_> = good.iter().map(|&i| pks[i]).collect();
+        let good_msgs: Vec<_> = good.iter().map(|&i| msgs[i].as_ref()).collect();
+        let good_sigs: Vec<_> = good.iter().map(|&i| sigs[i]).collect();
+        bad.extend(
+            Self::batch_find_invalid_prevalidated(&good_pks, &good_msgs, &good_sigs)?
+                .into_iter()
+                .map(|j| good[j]),
+        );
+        bad.sort_unstable();
+        Ok(bad)
+    }
+
+    /// Like `batch_find_invalid`, but skips `key_validate` on the public keys
+    fn batch_find_invalid_prevalidated<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        msgs: &[B],
+        sigs: &[SigT<Self>],
+    ) -> Result<Vec<usize>, BlsError> {
+        Self::Scheme::batch_find_invalid_prevalidated::<Self, B>(pks, msgs, sigs)
+    }
+}
+
+/// A proof-of-possession ciphersuite, which also needs a tag for proofs of possession
+pub trait PopCiphersuite: Ciphersuite<Scheme = Pop> {
+    /// Domain separation tag for proofs of possession
+    const POP_DST: &'static [u8];
+
+    /// Prove possession of the public key for the secret exponent `x_prime`
+    fn pop_prove(x_prime: ScalarT<SigT<Self>>) -> SigT<Self> {
+        let mut pk = PkT::<Self>::one();
+        pk.mul_assign(x_prime);
+        <SigT<Self> as BLSSigCore<Self::Expand>>::core_sign(
+            x_prime,
+            pk.to_compressed(),
+            Self::POP_DST,
+        )
+    }
+
+    /// Check a proof of possession
+    fn pop_verify(pk: PkT<Self>, proof: SigT<Self>) -> bool {
+        Self::try_pop_verify(pk, proof).is_ok()
+    }
+
+    /// Like `pop_verify`, but reports why verification failed
+    fn try_pop_verify(pk: PkT<Self>, proof: SigT<Self>) -> Result<(), BlsError> {
+        <SigT<Self> as BLSSigCore<Self::Expand>>::try_core_verify(
+            pk,
+            proof,
+            pk.to_compressed(),
+            Self::POP_DST,
+        )
+    }
+
+    /// Verify a signature on one message by all of `pks`.
+    /// The caller must have checked a proof of possession for every key.
+    fn multisig_verify<B: AsRef<[u8]>>(pks: &[PkT<Self>], sig: SigT<Self>, msg: B) -> bool {
+        Self::try_multisig_verify(pks, sig, msg).is_ok()
+    }
+
+    /// Like `multisig_verify`, but reports why verification failed
+    fn try_multisig_verify<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        sig: SigT<Self>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        for pk in pks {
+            <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)?;
+        }
+        Self::try_multisig_verify_prevalidated(pks, sig, msg)
+    }
+
+    /// Like `try_multisig_verify`, but skips `key_validate` on the individual public keys.
+    /// The aggregate public key is still rejected if it is the identity.
+    fn try_multisig_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[PkT<Self>],
+        sig: SigT<Self>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        if pks.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        let apk = _agg_help(pks);
+        if apk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        Self::Scheme::try_verify_prevalidated::<Self, B>(apk, sig, msg)
+    }
+
+    /// Verify a weighted multisignature: `sig` must be sum_i w_i * sig_i, where sig_i is
+    /// a signature on `msg` under pk_i and (pk_i, w_i) ranges over `signers`. Fails if the
+    /// total weight of `signers` is below `min_weight`.
+    /// The caller must have checked a proof of possession for every key.
+    fn weighted_multisig_verify<B: AsRef<[u8]>>(
+        signers: &[(PkT<Self>, u64)],
+        sig: SigT<Self>,
+        msg: B,
+        min_weight: u64,
+    ) -> bool {
+        Self::try_weighted_multisig_verify(signers, sig, msg, min_weight).is_ok()
+    }
+
+    /// Like `weighted_multisig_verify`, but reports why verification failed
+    fn try_weighted_multisig_verify<B: AsRef<[u8]>>(
+        signers: &[(PkT<Self>, u64)],
+        sig: SigT<Self>,
+        msg: B,
+        min_weight: u64,
+    ) -> Result<(), BlsError> {
+        for (pk, _) in signers {
+            <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)?;
+        }
+        Self::try_weighted_multisig_verify_prevalidated(signers, sig, msg, min_weight)
+    }
+
+    /// Like `try_weighted_multisig_verify`, but skips `key_validate` on the public keys
+    fn try_weighted_multisig_verify_prevalidated<B: AsRef<[u8]>>(
+        signers: &[(PkT<Self>, u64)],
+        sig: SigT<Self>,
+        msg: B,
+        min_weight: u64,
+    ) -> Result<(), BlsError> {
+        let apk = _weighted_apk(signers, min_weight)?;
+        Self::Scheme::try_verify_prevalidated::<Self, B>(apk, sig, msg)
+    }
+
+    /// Like `multisig_verify`, but takes the public keys already summed in an `Aggregator`.
+    /// The caller must have run `key_validate` on, and checked a proof of possession for,
+    /// every key that went into it.
+    fn multisig_verify_aggregated<B: AsRef<[u8]>>(
+        apk: &Aggregator<PkT<Self>>,
+        sig: SigT<Self>,
+        msg: B,
+    ) -> bool {
+        Self::try_multisig_verify_aggregated(apk, sig, msg).is_ok()
+    }
+
+    /// Like `multisig_verify_aggregated`, but reports why verification failed
+    fn try_multisig_verify_aggregated<B: AsRef<[u8]>>(
+        apk: &Aggregator<PkT<Self>>,
+        sig: SigT<Self>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        if apk.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        if apk.as_point().is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        Self::Scheme::try_verify_prevalidated::<Self, B>(*apk.as_point(), sig, msg)
+    }
+
+    /// Like `multisig_verify`, but for a message that was already hashed
+    fn multisig_verify_hashed(
+        pks: &[PkT<Self>],
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> bool {
+        Self::try_multisig_verify_hashed(pks, sig, hashed).is_ok()
+    }
+
+    /// Like `multisig_verify_hashed`, but reports why verification failed
+    fn try_multisig_verify_hashed(
+        pks: &[PkT<Self>],
+        sig: SigT<Self>,
+        hashed: &HashedMessage<SigT<Self>, Self>,
+    ) -> Result<(), BlsError> {
+        if pks.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        for pk in pks {
+            <SigT<Self> as BLSSigCore<Self::Expand>>::key_validate(pk)?;
+        }
+        let apk = _agg_help(pks);
+        if apk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        Self::try_verify_hashed_prevalidated(apk, sig, hashed)
+    }
+}
+
+/// Declare a new ciphersuite.
+///
+/// ```
+/// # #[macro_use] extern crate bls_sigs_ref;
+/// # extern crate pairing_plus;
+/// # extern crate sha2;
+/// use bls_sigs_ref::{Aug, Ciphersuite, Pop, PopCiphersuite};
+/// use pairing_plus::bls12_381::G1;
+/// use pairing_plus::hash_to_field::ExpandMsgXmd;
+/// use sha2::Sha512;
+///
+/// ciphersuite! {
+///     /// Message augmentation with SHA-512 and an application-specific tag
+///     pub MyAugSuite: G1, ExpandMsgXmd<Sha512>, Aug, b"MY_APP_BLS_SIG_BLS12381G1_XMD:SHA-512_SSWU_RO_AUG_"
+/// }
+///
+/// ciphersuite! {
+///     /// Proof of possession with SHA-512 and application-specific tags
+///     pub MyPopSuite: G1, ExpandMsgXmd<Sha512>, Pop, b"MY_APP_BLS_SIG_BLS12381G1_XMD:SHA-512_SSWU_RO_POP_",
+///     pop: b"MY_APP_BLS_POP_BLS12381G1_XMD:SHA-512_SSWU_RO_POP_"
+/// }
+///
+/// # fn main() {
+/// let (x_prime, pk) = MyAugSuite::keygen("this is the key");
+/// let sig = MyAugSuite::sign(x_prime, "this is the message");
+/// assert!(MyAugSuite::verify(pk, sig, "this is the message"));
+///
+/// let (x_prime, pk) = MyPopSuite::keygen("this is the key");
+/// assert!(MyPopSuite::pop_verify(pk, MyPopSuite::pop_prove(x_prime)));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ciphersuite {
+    ($(#[$attr:meta])* pub $name:ident: $group:ty, $expand:ty, Pop, $dst:expr, pop: $pop_dst:expr) => {
+        ciphersuite!($(#[$attr])* pub $name: $group, $expand, Pop, $dst);
+
+        impl $crate::PopCiphersuite for $name {
+            const POP_DST: &'static [u8] = $pop_dst;
+        }
+    };
+    ($(#[$attr:meta])* pub $name:ident: $group:ty, $expand:ty, $scheme:ident, $dst:expr) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub enum $name {}
+
+        impl $crate::Ciphersuite for $name {
+            type Expand = $expand;
+            type Group = $group;
+            type PkGroup = <$group as $crate::BLSSigCore<$expand>>::PKType;
+            type Scheme = $crate::$scheme;
+            const DST: &'static [u8] = $dst;
+        }
+    };
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_
+    pub G1XmdSha256Basic: G1, ExpandMsgXmd<Sha256>, Basic,
+    <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::CSUITE
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_AUG_
+    pub G1XmdSha256Aug: G1, ExpandMsgXmd<Sha256>, Aug,
+    <G1 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::CSUITE
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_
+    pub G1XmdSha256Pop: G1, ExpandMsgXmd<Sha256>, Pop,
+    <G1 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::CSUITE,
+    pop: <G1 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::CSUITE_POP
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_
+    pub G2XmdSha256Basic: G2, ExpandMsgXmd<Sha256>, Basic,
+    <G2 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::CSUITE
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_
+    pub G2XmdSha256Aug: G2, ExpandMsgXmd<Sha256>, Aug,
+    <G2 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::CSUITE
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_
+    pub G2XmdSha256Pop: G2, ExpandMsgXmd<Sha256>, Pop,
+    <G2 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::CSUITE,
+    pop: <G2 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::CSUITE_POP
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G1_XOF:SHAKE-256_SSWU_RO_NUL_
+    pub G1XofShake256Basic: G1, ExpandMsgXof<Shake256>, Basic,
+    b"BLS_SIG_BLS12381G1_XOF:SHAKE-256_SSWU_RO_NUL_"
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G1_XOF:SHAKE-256_SSWU_RO_AUG_
+    pub G1XofShake256Aug: G1, ExpandMsgXof<Shake256>, Aug,
+    b"BLS_SIG_BLS12381G1_XOF:SHAKE-256_SSWU_RO_AUG_"
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G1_XOF:SHAKE-256_SSWU_RO_POP_
+    pub G1XofShake256Pop: G1, ExpandMsgXof<Shake256>, Pop,
+    b"BLS_SIG_BLS12381G1_XOF:SHAKE-256_SSWU_RO_POP_",
+    pop: b"BLS_POP_BLS12381G1_XOF:SHAKE-256_SSWU_RO_POP_"
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_NUL_
+    pub G2XofShake256Basic: G2, ExpandMsgXof<Shake256>, Basic,
+    b"BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_NUL_"
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_AUG_
+    pub G2XofShake256Aug: G2, ExpandMsgXof<Shake256>, Aug,
+    b"BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_AUG_"
+}
+
+ciphersuite! {
+    /// BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_POP_
+    pub G2XofShake256Pop: G2, ExpandMsgXof<Shake256>, Pop,
+    b"BLS_SIG_BLS12381G2_XOF:SHAKE-256_SSWU_RO_POP_",
+    pop: b"BLS_POP_BLS12381G2_XOF:SHAKE-256_SSWU_RO_POP_"
+}
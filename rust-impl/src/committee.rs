@@ -0,0 +1,161 @@
+/*!
+Committees of signers whose proofs of possession have been checked
+
+In the proof of possession scheme, every public key must come with a valid proof before
+its signatures can be aggregated with others. A `Committee` is an ordered list of such
+keys, so that a signer can be named by its index, as in a `BitfieldAggregate`.
+
+A committee also caches the sum of all its members' keys. Verifying a multisignature by
+the whole committee then costs no group additions, and the aggregate key of a subset of
+signers is computed either as a sum over the signers or as the full sum minus the
+non-signers, whichever touches fewer keys. Members can join and leave; the cached sum is
+updated in place rather than recomputed.
+*/
+
+use ciphersuite::{PkT, PopCiphersuite, SchemeKind, SigT};
+use error::BlsError;
+use pairing_plus::CurveProjective;
+use signature::_agg_help;
+use std::fmt;
+
+/// An ordered committee of public keys for the proof of possession suite `C`.
+/// Verification trusts that every key has passed `key_validate` and had its proof of
+/// possession checked. `new` and `join` check both; the `_prevalidated` constructors
+/// leave them to the caller.
+pub struct Committee<C: PopCiphersuite> {
+    members: Vec<PkT<C>>,
+    apk: PkT<C>,
+}
+
+impl<C: PopCiphersuite> Committee<C> {
+    /// A committee of the given (public key, proof of possession) pairs, in order.
+    /// Fails if any proof does not verify.
+    pub fn new(members: &[(PkT<C>, SigT<C>)]) -> Result<Self, BlsError> {
+        for (pk, proof) in members {
+            C::try_pop_verify(*pk, *proof)?;
+        }
+        Ok(Self::from_prevalidated(
+            members.iter().map(|(pk, _)| *pk).collect(),
+        ))
+    }
+
+    /// A committee of public keys that the caller already checked with `key_validate`
+    /// and whose proofs of possession the caller already verified, e.g., at registration
+    /// time. Nothing is checked here.
+    pub fn from_prevalidated(members: Vec<PkT<C>>) -> Self {
+        let apk = _agg_help(&members[..]);
+        Committee { members, apk }
+    }
+
+    /// Add a member at the end of the committee, if its proof of possession verifies.
+    /// Returns the new member's index.
+    pub fn join(&mut self, pk: PkT<C>, proof: SigT<C>) -> Result<usize, BlsError> {
+        C::try_pop_verify(pk, proof)?;
+        Ok(self.join_prevalidated(pk))
+    }
+
+    /// Add a member that the caller already checked with `key_validate` and whose proof
+    /// of possession the caller already verified. Nothing is checked here.
+    /// Returns the new member's index.
+    pub fn join_prevalidated(&mut self, pk: PkT<C>) -> usize {
+        self.apk.add_assign(&pk);
+        self.members.push(pk);
+        self.members.len() - 1
+    }
+
+    /// Remove member `index` and return its public key. Every later member's index
+    /// decreases by one, so bitmaps made for the old committee no longer apply.
+    pub fn leave(&mut self, index: usize) -> Result<PkT<C>, BlsError> {
+        if index >= self.members.len() {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        let pk = self.members.remove(index);
+        self.apk.sub_assign(&pk);
+        Ok(pk)
+    }
+
+    /// The number of members
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the committee has no members
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// The members' public keys, in order
+    pub fn members(&self) -> &[PkT<C>] {
+        &self.members[..]
+    }
+
+    /// The sum of every member's public key
+    pub fn aggregate_public_key(&self) -> &PkT<C> {
+        &self.apk
+    }
+
+    /// Verify a multisignature on `msg` by every member of the committee
+    pub fn multisig_verify<B: AsRef<[u8]>>(&self, sig: SigT<C>, msg: B) -> bool {
+        self.try_multisig_verify(sig, msg).is_ok()
+    }
+
+    /// Like `multisig_verify`, but reports why verification failed
+    pub fn try_multisig_verify<B: AsRef<[u8]>>(
+        &self,
+        sig: SigT<C>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        if self.members.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        if self.apk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        C::Scheme::try_verify_prevalidated::<C, B>(self.apk, sig, msg)
+    }
+
+    /// The aggregate public key of the members whose indices are set in `bits`, a bitmap
+    /// over the committee in the format of `BitfieldAggregate`
+    pub(crate) fn signer_apk(&self, bits: &[u8]) -> PkT<C> {
+        let n = self.members.len();
+        let signers = (0..n).filter(|&i| _get_bit(bits, i)).count();
+        // when most members signed, subtract the few who did not from the cached sum
+        let (mut ret, want) = if 2 * signers > n {
+            (self.apk, false)
+        } else {
+            (PkT::<C>::zero(), true)
+        };
+        for (i, pk) in self.members.iter().enumerate() {
+            if _get_bit(bits, i) == want {
+                if want {
+                    ret.add_assign(pk);
+                } else {
+                    ret.sub_assign(pk);
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl<C: PopCiphersuite> Clone for Committee<C> {
+    fn clone(&self) -> Self {
+        Committee {
+            members: self.members.clone(),
+            apk: self.apk,
+        }
+    }
+}
+
+impl<C: PopCiphersuite> fmt::Debug for Committee<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Committee")
+            .field("members", &self.members)
+            .finish()
+    }
+}
+
+// bit i of a bitmap, least significant bit first within each byte
+pub(crate) fn _get_bit(bits: &[u8], i: usize) -> bool {
+    bits[i / 8] & (1 << (i % 8)) != 0
+}
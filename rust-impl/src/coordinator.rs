@@ -0,0 +1,217 @@
+/*!
+Collecting partial signatures from a threshold committee
+
+`BLSSignatureThreshold::combine` trusts its input: one bad partial spoils the result, and
+nothing says which. A `ThresholdCoordinator` sits between the transport and `combine`.
+It takes serialized partial signatures as they arrive, in any order and for any number of
+messages at once, and keeps separate state for each message. Every partial is checked
+against its share's public key with `core_verify`, under the Basic scheme's ciphersuite
+tag, before it is kept, and a message is only tracked once one of its partials verifies.
+Repeats are dropped, and signers whose partials fail are recorded until they send one that
+verifies.
+Once `threshold` valid partials for a message are in, the coordinator scales each by its
+Lagrange coefficient, `aggregate`s them into the signature, and ignores later partials.
+
+Misbehavior is attributed by the index inside each message, so the report is only
+meaningful if the transport authenticates senders.
+*/
+
+use encoding::PointEncoding;
+use error::BlsError;
+use pairing_plus::hash_to_field::ExpandMsg;
+use pairing_plus::CurveProjective;
+use signature::{BLSSigCore, BLSSignatureBasic};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use threshold::{_lagrange_at_zero, BLSSignatureThreshold, PartialSignature, PublicKeyShare};
+
+/// Where signing one message stands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningStatus<G: CurveProjective> {
+    /// Fewer than `threshold` valid partials are in; this many are
+    Pending(usize),
+    /// The signature is complete
+    Complete(G),
+}
+
+/// The signers who sent valid and invalid partials for a message that was not signed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MisbehaviorReport {
+    valid: Vec<u32>,
+    invalid: Vec<u32>,
+}
+
+impl MisbehaviorReport {
+    /// The indices of the signers whose partials verified, in increasing order
+    pub fn valid(&self) -> &[u32] {
+        &self.valid[..]
+    }
+
+    /// The indices of the signers who sent partials that did not verify and none that
+    /// did, in increasing order
+    pub fn invalid(&self) -> &[u32] {
+        &self.invalid[..]
+    }
+}
+
+// the state of one message
+#[derive(Debug)]
+struct SigningState<G: CurveProjective> {
+    partials: BTreeMap<u32, G>,
+    invalid: BTreeSet<u32>,
+    sig: Option<G>,
+}
+
+/// Collects partial signatures from the committee whose public key shares it was given,
+/// and combines each message's first `threshold` valid partials
+pub struct ThresholdCoordinator<G: BLSSignatureThreshold<X>, X: ExpandMsg> {
+    threshold: usize,
+    pk_shares: BTreeMap<u32, G::PKType>,
+    requests: HashMap<Vec<u8>, SigningState<G>>,
+    _expand: PhantomData<X>,
+}
+
+impl<G: BLSSignatureThreshold<X> + PointEncoding, X: ExpandMsg> ThresholdCoordinator<G, X> {
+    /// A coordinator for the committee with the given public key shares.
+    /// Fails if `threshold` is zero or exceeds the number of shares, if two shares have
+    /// the same index, or if any share fails `key_validate`.
+    pub fn new(
+        threshold: usize,
+        pk_shares: &[PublicKeyShare<G::PKType>],
+    ) -> Result<Self, BlsError> {
+        if threshold == 0 || threshold > pk_shares.len() {
+            return Err(BlsError::InvalidThreshold);
+        }
+        let mut shares = BTreeMap::new();
+        for pk_share in pk_shares {
+            <G as BLSSigCore<X>>::key_validate(pk_share.as_point())?;
+            if shares
+                .insert(pk_share.index(), *pk_share.as_point())
+                .is_some()
+            {
+                return Err(BlsError::DuplicateSigner);
+            }
+        }
+        Ok(ThresholdCoordinator {
+            threshold,
+            pk_shares: shares,
+            requests: HashMap::new(),
+            _expand: PhantomData,
+        })
+    }
+
+    /// Accept a serialized partial signature (the output of `PartialSignature::to_bytes`)
+    /// on `msg`, and report where signing `msg` stands.
+    ///
+    /// Once `msg` is signed, later partials are ignored and the signature is returned again.
+    /// Otherwise this fails, and drops the partial, if it does not decode, if its signer
+    /// is outside the committee, if its signer already sent a valid partial
+    /// (`DuplicateSigner`), or if it does not verify. In the last case the signer is
+    /// recorded as misbehaving if `msg` already has a valid partial; otherwise `msg` is
+    /// not tracked yet, and the returned error is the only record. A valid partial clears
+    /// the signer's record, e.g., if an earlier one was corrupted in transit.
+    pub fn receive<B: AsRef<[u8]>>(
+        &mut self,
+        msg: B,
+        bytes: &[u8],
+    ) -> Result<SigningStatus<G>, BlsError> {
+        let msg = msg.as_ref();
+        if let Some(sig) = self.requests.get(msg).and_then(|state| state.sig) {
+            return Ok(SigningStatus::Complete(sig));
+        }
+        let partial = PartialSignature::<G>::from_bytes(bytes)?;
+        let index = partial.index();
+        let pk = *self
+            .pk_shares
+            .get(&index)
+            .ok_or(BlsError::InvalidSignerIndex)?;
+        if let Some(state) = self.requests.get(msg) {
+            if state.partials.contains_key(&index) {
+                return Err(BlsError::DuplicateSigner);
+            }
+        }
+        if let Err(e) = <G as BLSSigCore<X>>::try_core_verify_prevalidated(
+            pk,
+            *partial.as_point(),
+            msg,
+            <G as BLSSignatureBasic<X>>::CSUITE,
+        ) {
+            // a message is only tracked once some partial for it verifies, so that
+            // invalid partials cannot make the coordinator hold state for new messages
+            if let Some(state) = self.requests.get_mut(msg) {
+                state.invalid.insert(index);
+            }
+            return Err(e);
+        }
+        let state = self
+            .requests
+            .entry(msg.to_vec())
+            .or_insert_with(|| SigningState {
+                partials: BTreeMap::new(),
+                invalid: BTreeSet::new(),
+                sig: None,
+            });
+        state.invalid.remove(&index);
+        state.partials.insert(index, *partial.as_point());
+        if state.partials.len() < self.threshold {
+            return Ok(SigningStatus::Pending(state.partials.len()));
+        }
+        let sig = _combine_help::<G, X>(&state.partials)?;
+        state.sig = Some(sig);
+        Ok(SigningStatus::Complete(sig))
+    }
+
+    /// Where signing `msg` stands, or `None` if no valid partial for it has arrived
+    pub fn status<B: AsRef<[u8]>>(&self, msg: B) -> Option<SigningStatus<G>> {
+        self.requests
+            .get(msg.as_ref())
+            .map(|state| match state.sig {
+                Some(sig) => SigningStatus::Complete(sig),
+                None => SigningStatus::Pending(state.partials.len()),
+            })
+    }
+
+    /// Stop tracking `msg`, e.g., after a timeout. Returns the signature if it is complete,
+    /// and otherwise a report of which signers sent valid and invalid partials.
+    pub fn finish<B: AsRef<[u8]>>(&mut self, msg: B) -> Result<G, MisbehaviorReport> {
+        match self.requests.remove(msg.as_ref()) {
+            None => Err(MisbehaviorReport {
+                valid: Vec::new(),
+                invalid: Vec::new(),
+            }),
+            Some(SigningState { sig: Some(sig), .. }) => Ok(sig),
+            Some(state) => Err(MisbehaviorReport {
+                valid: state.partials.keys().cloned().collect(),
+                invalid: state.invalid.into_iter().collect(),
+            }),
+        }
+    }
+}
+
+impl<G: BLSSignatureThreshold<X>, X: ExpandMsg> fmt::Debug for ThresholdCoordinator<G, X> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ThresholdCoordinator")
+            .field("threshold", &self.threshold)
+            .field("pk_shares", &self.pk_shares)
+            .field("requests", &self.requests)
+            .finish()
+    }
+}
+
+// sum_j lambda_j * partials[j], as an aggregate of the scaled partials
+fn _combine_help<G: BLSSignatureThreshold<X>, X: ExpandMsg>(
+    partials: &BTreeMap<u32, G>,
+) -> Result<G, BlsError> {
+    let indices: Vec<u32> = partials.keys().cloned().collect();
+    let scaled: Vec<G> = _lagrange_at_zero(&indices[..])?
+        .into_iter()
+        .zip(partials.values())
+        .map(|(lambda, partial)| {
+            let mut ret = *partial;
+            ret.mul_assign(lambda);
+            ret
+        })
+        .collect();
+    Ok(<G as BLSSigCore<X>>::aggregate(&scaled[..]))
+}
@@ -0,0 +1,679 @@
+/*!
+Distributed key generation with Feldman verifiable secret sharing
+
+`split_secret` needs a dealer who knows the secret key. This module instead runs the
+Joint-Feldman protocol (Pedersen, Eurocrypt '91), in which no one ever learns it. Each of
+the n participants deals a random polynomial f_i of degree t - 1. It sends the share f_i(j)
+privately to participant j and broadcasts Feldman commitments to the coefficients of f_i,
+i.e., the public key group's generator times each coefficient. Participant j checks every
+share it receives against the dealer's commitments, and broadcasts a complaint against
+each dealer whose share is missing or wrong. An accused dealer answers by broadcasting the
+disputed share. Dealers that do not commit, leave a complaint unanswered, or answer with a
+share that does not match their commitments are disqualified.
+
+The joint secret is the sum of the qualified dealers' secrets. Participant j's share of it
+is the sum of the shares that j received from them, and the joint public key is the sum of
+their commitments to their constant terms. The shares work with `BLSSignatureThreshold`
+exactly like the output of `split_secret`. Note that a rushing adversary can bias the
+distribution of the joint public key (Gennaro et al., J. Cryptology 2007).
+
+The messages are, in order: `DkgCommitment` (broadcast) and `DkgShare` (private, e.g., over
+an encrypted channel); then `DkgComplaint` (broadcast); then `DkgShare` again, broadcast as
+the answer to a complaint. `DkgParticipant` consumes them and produces a `DkgOutput`.
+*/
+
+use encoding::PointEncoding;
+use error::BlsError;
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_plus::bls12_381::{Fr, FrRepr};
+use pairing_plus::CurveProjective;
+use rand::Rng;
+use signature::_msm_help;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use threshold::{_poly_eval, _random_scalar, PublicKeyShare, SecretShare};
+use zeroize::Zeroize;
+
+/// A dealer's Feldman commitments to the coefficients of its polynomial, lowest degree
+/// first. The first commitment is the dealer's contribution to the joint public key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DkgCommitment<P: CurveProjective> {
+    dealer: u32,
+    coeffs: Vec<P>,
+}
+
+impl<P: CurveProjective<Scalar = Fr>> DkgCommitment<P> {
+    /// A commitment from the given dealer. Fails if `dealer` is zero or if there are no
+    /// coefficients.
+    pub fn new(dealer: u32, coeffs: Vec<P>) -> Result<Self, BlsError> {
+        if dealer == 0 {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        if coeffs.is_empty() {
+            return Err(BlsError::InvalidCommitment);
+        }
+        Ok(DkgCommitment { dealer, coeffs })
+    }
+
+    /// The index of the dealer
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// The commitments, lowest degree first
+    pub fn coefficients(&self) -> &[P] {
+        &self.coeffs[..]
+    }
+
+    /// The public key of the dealer's secret, i.e., the commitment to the constant term
+    pub fn public_key(&self) -> P {
+        self.coeffs[0]
+    }
+
+    /// Check a share from this dealer against the commitments
+    pub fn verify_share(&self, share: &DkgShare) -> Result<(), BlsError> {
+        if share.dealer != self.dealer {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        _share_check(&self.coeffs[..], share.recipient, &share.value)
+    }
+}
+
+impl<P: PointEncoding + CurveProjective<Scalar = Fr>> DkgCommitment<P> {
+    /// Serialize as the dealer's index, a 4-byte big-endian integer, followed by the
+    /// compressed commitments
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let clen = P::zero().to_compressed().as_ref().len();
+        let mut ret = Vec::with_capacity(4 + clen * self.coeffs.len());
+        ret.extend_from_slice(&self.dealer.to_be_bytes());
+        for c in &self.coeffs {
+            ret.extend_from_slice(c.to_compressed().as_ref());
+        }
+        ret
+    }
+
+    /// Deserialize the output of `to_bytes`. Fails if the length is wrong, if the dealer
+    /// is zero, if there are no commitments, or if any commitment does not decode.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let mut enc = P::zero().to_compressed();
+        let clen = enc.as_ref().len();
+        if bytes.len() < 4 || !(bytes.len() - 4).is_multiple_of(clen) {
+            return Err(BlsError::InvalidEncoding);
+        }
+        let mut dealer = [0u8; 4];
+        dealer.copy_from_slice(&bytes[..4]);
+        let coeffs = bytes[4..]
+            .chunks(clen)
+            .map(|c| {
+                enc.as_mut().copy_from_slice(c);
+                P::from_compressed(&enc)
+            })
+            .collect::<Result<Vec<P>, BlsError>>()?;
+        Self::new(u32::from_be_bytes(dealer), coeffs)
+    }
+}
+
+/// A dealer's share for one recipient: the dealer's polynomial evaluated at the
+/// recipient's index. The value is zeroized on drop, and this type is deliberately not
+/// `Copy`.
+pub struct DkgShare {
+    dealer: u32,
+    recipient: u32,
+    value: Fr,
+}
+
+impl DkgShare {
+    /// A share from `dealer` to `recipient`. Fails if either index is zero.
+    pub fn new(dealer: u32, recipient: u32, value: Fr) -> Result<Self, BlsError> {
+        if dealer == 0 || recipient == 0 {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        Ok(DkgShare {
+            dealer,
+            recipient,
+            value,
+        })
+    }
+
+    /// The index of the dealer
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// The index of the recipient
+    pub fn recipient(&self) -> u32 {
+        self.recipient
+    }
+
+    /// The value of the share
+    pub fn value(&self) -> &Fr {
+        &self.value
+    }
+
+    /// Serialize as the dealer's and recipient's indices, 4-byte big-endian integers,
+    /// followed by the value as a 32-byte big-endian integer
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut ret = [0u8; 40];
+        ret[..4].copy_from_slice(&self.dealer.to_be_bytes());
+        ret[4..8].copy_from_slice(&self.recipient.to_be_bytes());
+        // writing 32 bytes into a 32-byte buffer cannot fail
+        self.value.into_repr().write_be(&mut ret[8..]).unwrap();
+        ret
+    }
+
+    /// Deserialize the output of `to_bytes`. Fails if either index is zero or if the
+    /// value is not less than r.
+    pub fn from_bytes(bytes: &[u8; 40]) -> Result<Self, BlsError> {
+        let mut dealer = [0u8; 4];
+        dealer.copy_from_slice(&bytes[..4]);
+        let mut recipient = [0u8; 4];
+        recipient.copy_from_slice(&bytes[4..8]);
+        let mut repr = FrRepr::default();
+        // reading 32 bytes from a 32-byte buffer cannot fail
+        repr.read_be(&bytes[8..]).unwrap();
+        let value = Fr::from_repr(repr).map_err(|_| BlsError::InvalidEncoding)?;
+        Self::new(
+            u32::from_be_bytes(dealer),
+            u32::from_be_bytes(recipient),
+            value,
+        )
+    }
+}
+
+impl Clone for DkgShare {
+    fn clone(&self) -> Self {
+        DkgShare {
+            dealer: self.dealer,
+            recipient: self.recipient,
+            value: self.value,
+        }
+    }
+}
+
+impl Zeroize for DkgShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for DkgShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl PartialEq for DkgShare {
+    fn eq(&self, other: &Self) -> bool {
+        self.dealer == other.dealer
+            && self.recipient == other.recipient
+            && self.value == other.value
+    }
+}
+
+impl Eq for DkgShare {}
+
+impl fmt::Debug for DkgShare {
+    // never print the share's value
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DkgShare({}, {}, <redacted>)",
+            self.dealer, self.recipient
+        )
+    }
+}
+
+/// A complaint that `complainer` did not receive a valid share from `dealer`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DkgComplaint {
+    dealer: u32,
+    complainer: u32,
+}
+
+impl DkgComplaint {
+    /// A complaint by `complainer` against `dealer`. Fails if either index is zero.
+    pub fn new(dealer: u32, complainer: u32) -> Result<Self, BlsError> {
+        if dealer == 0 || complainer == 0 {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        Ok(DkgComplaint { dealer, complainer })
+    }
+
+    /// The index of the accused dealer
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// The index of the participant who complained
+    pub fn complainer(&self) -> u32 {
+        self.complainer
+    }
+
+    /// Serialize as the dealer's and complainer's indices, 4-byte big-endian integers
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut ret = [0u8; 8];
+        ret[..4].copy_from_slice(&self.dealer.to_be_bytes());
+        ret[4..].copy_from_slice(&self.complainer.to_be_bytes());
+        ret
+    }
+
+    /// Deserialize the output of `to_bytes`. Fails if the length is wrong or if either
+    /// index is zero.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        if bytes.len() != 8 {
+            return Err(BlsError::InvalidEncoding);
+        }
+        let mut dealer = [0u8; 4];
+        dealer.copy_from_slice(&bytes[..4]);
+        let mut complainer = [0u8; 4];
+        complainer.copy_from_slice(&bytes[4..]);
+        Self::new(u32::from_be_bytes(dealer), u32::from_be_bytes(complainer))
+    }
+}
+
+// the committed polynomial evaluated at x, in the exponent
+pub(crate) fn _commitment_eval<P: CurveProjective<Scalar = Fr>>(coeffs: &[P], x: u32) -> P {
+    let x = Fr::from_repr(u64::from(x).into()).unwrap();
+    let mut ret = P::zero();
+    for c in coeffs.iter().rev() {
+        ret.mul_assign(x);
+        ret.add_assign(c);
+    }
+    ret
+}
+
+// check that value is the committed polynomial evaluated at x
+pub(crate) fn _share_check<P: CurveProjective<Scalar = Fr>>(
+    coeffs: &[P],
+    x: u32,
+    value: &Fr,
+) -> Result<(), BlsError> {
+    let mut lhs = P::one();
+    lhs.mul_assign(*value);
+    if lhs == _commitment_eval(coeffs, x) {
+        Ok(())
+    } else {
+        Err(BlsError::InvalidShare)
+    }
+}
+
+// deal a random polynomial with constant term `secret` to participants 1 through n
+pub(crate) fn _deal_help<P: CurveProjective<Scalar = Fr>, R: Rng>(
+    dealer: u32,
+    secret: Fr,
+    threshold: usize,
+    n: usize,
+    rng: &mut R,
+) -> (DkgCommitment<P>, Vec<DkgShare>) {
+    let mut coeffs = Vec::with_capacity(threshold);
+    coeffs.push(secret);
+    for _ in 1..threshold {
+        coeffs.push(_random_scalar(rng));
+    }
+    let commitments = coeffs
+        .iter()
+        .map(|c| {
+            let mut pt = P::one();
+            pt.mul_assign(*c);
+            pt
+        })
+        .collect();
+    let shares = (1..=n as u32)
+        .map(|j| DkgShare {
+            dealer,
+            recipient: j,
+            value: _poly_eval(&coeffs[..], j),
+        })
+        .collect();
+    for c in coeffs.iter_mut() {
+        c.zeroize();
+    }
+    let commitment = DkgCommitment {
+        dealer,
+        coeffs: commitments,
+    };
+    (commitment, shares)
+}
+
+/// The result of a DKG for one participant: its secret share, plus public values that
+/// every honest participant agrees on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DkgOutput<P: CurveProjective> {
+    share: SecretShare,
+    coeffs: Vec<P>,
+    qualified: Vec<u32>,
+}
+
+impl<P: CurveProjective<Scalar = Fr>> DkgOutput<P> {
+    pub(crate) fn new(
+        index: u32,
+        value: Fr,
+        coeffs: Vec<P>,
+        qualified: Vec<u32>,
+    ) -> Result<Self, BlsError> {
+        Ok(DkgOutput {
+            share: SecretShare::new(index, value)?,
+            coeffs,
+            qualified,
+        })
+    }
+
+    /// This participant's share of the joint secret
+    pub fn secret_share(&self) -> &SecretShare {
+        &self.share
+    }
+
+    /// The joint public key
+    pub fn public_key(&self) -> P {
+        self.coeffs[0]
+    }
+
+    /// The public key of share `index`, computed from the joint commitments.
+    /// Fails if `index` is zero.
+    pub fn public_key_share(&self, index: u32) -> Result<PublicKeyShare<P>, BlsError> {
+        PublicKeyShare::new(index, _commitment_eval(&self.coeffs[..], index))
+    }
+
+    /// Commitments to the joint polynomial, i.e., the sums of the qualified dealers'
+    /// commitments, lowest degree first
+    pub fn commitments(&self) -> &[P] {
+        &self.coeffs[..]
+    }
+
+    /// The indices of the qualified dealers, in increasing order
+    pub fn qualified(&self) -> &[u32] {
+        &self.qualified[..]
+    }
+}
+
+// a recipient's view of one round of verifiable secret sharing: dealers 1 through `dealers`
+// each share a polynomial of degree `threshold - 1` among recipients 1 through `recipients`
+#[derive(Debug)]
+pub(crate) struct VssState<P: CurveProjective> {
+    index: u32,
+    threshold: usize,
+    dealers: usize,
+    recipients: usize,
+    // if present, the commitment that each dealer's constant term must have
+    constants: Option<Vec<P>>,
+    commitments: BTreeMap<u32, DkgCommitment<P>>,
+    shares: BTreeMap<u32, DkgShare>,
+    // open complaints: dealer -> complainers
+    complaints: BTreeMap<u32, BTreeSet<u32>>,
+    disqualified: BTreeSet<u32>,
+}
+
+impl<P: CurveProjective<Scalar = Fr>> VssState<P> {
+    pub(crate) fn new(
+        index: u32,
+        threshold: usize,
+        dealers: usize,
+        recipients: usize,
+        constants: Option<Vec<P>>,
+    ) -> Result<Self, BlsError> {
+        if threshold == 0 || threshold > recipients || recipients > u32::MAX as usize {
+            return Err(BlsError::InvalidThreshold);
+        }
+        if index == 0 || index as usize > recipients || dealers > u32::MAX as usize {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        Ok(VssState {
+            index,
+            threshold,
+            dealers,
+            recipients,
+            constants,
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            complaints: BTreeMap::new(),
+            disqualified: BTreeSet::new(),
+        })
+    }
+
+    pub(crate) fn receive_commitment(
+        &mut self,
+        commitment: DkgCommitment<P>,
+    ) -> Result<(), BlsError> {
+        let dealer = commitment.dealer;
+        _check_index(dealer, self.dealers)?;
+        if commitment.coeffs.len() != self.threshold {
+            return Err(BlsError::InvalidCommitment);
+        }
+        if let Some(ref constants) = self.constants {
+            if commitment.coeffs[0] != constants[dealer as usize - 1] {
+                return Err(BlsError::InvalidCommitment);
+            }
+        }
+        if self.commitments.contains_key(&dealer) {
+            return Err(BlsError::DuplicateSigner);
+        }
+        self.commitments.insert(dealer, commitment);
+        Ok(())
+    }
+
+    pub(crate) fn receive_share(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        _check_index(share.dealer, self.dealers)?;
+        if share.recipient != self.index {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        if self.shares.contains_key(&share.dealer) {
+            return Err(BlsError::DuplicateSigner);
+        }
+        self.commitments
+            .get(&share.dealer)
+            .ok_or(BlsError::InvalidShare)?
+            .verify_share(&share)?;
+        self.shares.insert(share.dealer, share);
+        Ok(())
+    }
+
+    pub(crate) fn complaints(&self) -> Vec<DkgComplaint> {
+        self.commitments
+            .keys()
+            .filter(|d| !self.shares.contains_key(d))
+            .map(|&dealer| DkgComplaint {
+                dealer,
+                complainer: self.index,
+            })
+            .collect()
+    }
+
+    pub(crate) fn receive_complaint(&mut self, complaint: DkgComplaint) -> Result<(), BlsError> {
+        _check_index(complaint.dealer, self.dealers)?;
+        _check_index(complaint.complainer, self.recipients)?;
+        if !self
+            .complaints
+            .entry(complaint.dealer)
+            .or_default()
+            .insert(complaint.complainer)
+        {
+            return Err(BlsError::DuplicateSigner);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn receive_response(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        let open = self
+            .complaints
+            .get(&share.dealer)
+            .is_some_and(|c| c.contains(&share.recipient));
+        if !open {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        let valid = self
+            .commitments
+            .get(&share.dealer)
+            .ok_or(BlsError::InvalidShare)
+            .and_then(|c| c.verify_share(&share));
+        if let Err(e) = valid {
+            self.disqualified.insert(share.dealer);
+            return Err(e);
+        }
+        // unwrap is safe because the complaint is open
+        self.complaints
+            .get_mut(&share.dealer)
+            .unwrap()
+            .remove(&share.recipient);
+        if share.recipient == self.index {
+            self.shares.insert(share.dealer, share);
+        }
+        Ok(())
+    }
+
+    // the open complaints against `dealer`
+    pub(crate) fn complainers(&self, dealer: u32) -> Option<&BTreeSet<u32>> {
+        self.complaints.get(&dealer)
+    }
+
+    // the dealers who committed, answered every complaint, and never answered badly
+    pub(crate) fn qualified(&self) -> Vec<u32> {
+        self.commitments
+            .keys()
+            .filter(|d| !self.disqualified.contains(d))
+            .filter(|d| self.complaints.get(d).is_none_or(|c| c.is_empty()))
+            .cloned()
+            .collect()
+    }
+
+    // the shares and commitments of the `qualified` dealers, summed with the given weights
+    pub(crate) fn combine(
+        &self,
+        qualified: &[u32],
+        weights: &[Fr],
+    ) -> Result<(Fr, Vec<P>), BlsError> {
+        let mut value = Fr::zero();
+        for (d, w) in qualified.iter().zip(weights) {
+            // an unanswered complaint disqualifies, so we hold a valid share from d
+            let mut tmp = self.shares.get(d).ok_or(BlsError::InvalidShare)?.value;
+            tmp.mul_assign(w);
+            value.add_assign(&tmp);
+            tmp.zeroize();
+        }
+        let scalars: Vec<[u64; 4]> = weights.iter().map(|w| w.into_repr().0).collect();
+        let coeffs = (0..self.threshold)
+            .map(|k| {
+                let points: Vec<P> = qualified
+                    .iter()
+                    .map(|d| self.commitments[d].coeffs[k])
+                    .collect();
+                _msm_help(&points[..], &scalars[..])
+            })
+            .collect();
+        Ok((value, coeffs))
+    }
+}
+
+// the shares in `dealt` that answer complaints by `complainers`
+pub(crate) fn _responses_help(
+    complainers: Option<&BTreeSet<u32>>,
+    dealt: &[DkgShare],
+) -> Vec<DkgShare> {
+    match complainers {
+        None => Vec::new(),
+        Some(complainers) => dealt
+            .iter()
+            .filter(|s| complainers.contains(&s.recipient))
+            .cloned()
+            .collect(),
+    }
+}
+
+pub(crate) fn _check_index(index: u32, n: usize) -> Result<(), BlsError> {
+    if index == 0 || index as usize > n {
+        return Err(BlsError::InvalidSignerIndex);
+    }
+    Ok(())
+}
+
+/// One participant's view of a DKG among participants 1 through n
+#[derive(Debug)]
+pub struct DkgParticipant<P: CurveProjective> {
+    index: u32,
+    threshold: usize,
+    n: usize,
+    dealt: Vec<DkgShare>,
+    vss: VssState<P>,
+}
+
+impl<P: CurveProjective<Scalar = Fr>> DkgParticipant<P> {
+    /// Participant `index` in a DKG for `n` participants, any `threshold` of whom can sign.
+    /// Fails unless 1 <= `threshold` <= `n` < 2^32 and 1 <= `index` <= `n`.
+    pub fn new(index: u32, threshold: usize, n: usize) -> Result<Self, BlsError> {
+        Ok(DkgParticipant {
+            index,
+            threshold,
+            n,
+            dealt: Vec::new(),
+            vss: VssState::new(index, threshold, n, n, None)?,
+        })
+    }
+
+    /// The index of this participant
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Round 1: deal a random polynomial. Broadcast the commitment, and send share j to
+    /// participant j, including this participant's own share.
+    /// The shares are also kept, in order to answer complaints.
+    pub fn deal<R: Rng>(&mut self, rng: &mut R) -> (DkgCommitment<P>, Vec<DkgShare>) {
+        let secret = _random_scalar(rng);
+        let (commitment, shares) = _deal_help(self.index, secret, self.threshold, self.n, rng);
+        self.dealt = shares.clone();
+        (commitment, shares)
+    }
+
+    /// Round 1: accept a dealer's broadcast commitment. Fails if the dealer is outside the
+    /// committee, has already committed, or committed to a polynomial of the wrong degree.
+    pub fn receive_commitment(&mut self, commitment: DkgCommitment<P>) -> Result<(), BlsError> {
+        self.vss.receive_commitment(commitment)
+    }
+
+    /// Round 1: accept a share sent privately to this participant, after its dealer's
+    /// commitment. Fails with `InvalidShare` if the dealer has not committed or the share
+    /// does not match the commitment; such a share is dropped, and `complaints` will
+    /// accuse its dealer.
+    pub fn receive_share(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        self.vss.receive_share(share)
+    }
+
+    /// Round 2: complaints to broadcast, one against each committed dealer from whom this
+    /// participant holds no valid share
+    pub fn complaints(&self) -> Vec<DkgComplaint> {
+        self.vss.complaints()
+    }
+
+    /// Round 2: record a broadcast complaint, including this participant's own.
+    /// Fails if either index is outside the committee or if the complaint is a repeat.
+    pub fn receive_complaint(&mut self, complaint: DkgComplaint) -> Result<(), BlsError> {
+        self.vss.receive_complaint(complaint)
+    }
+
+    /// Round 3: shares to broadcast, answering the open complaints against this participant
+    pub fn responses(&self) -> Vec<DkgShare> {
+        _responses_help(self.vss.complainers(self.index), &self.dealt[..])
+    }
+
+    /// Round 3: check a dealer's broadcast answer to a complaint. A valid answer closes the
+    /// complaint, and if this participant complained, its share is the answer.
+    /// An invalid answer disqualifies the dealer, and this returns `InvalidShare`.
+    /// Fails with `InvalidSignerIndex` if there is no open complaint for the answer.
+    pub fn receive_response(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        self.vss.receive_response(share)
+    }
+
+    /// Finish the DKG. The qualified dealers are those who committed, answered every
+    /// complaint, and never answered with an invalid share; every honest participant
+    /// computes the same set. Fails with `EmptyInput` if no dealer qualified.
+    pub fn finish(self) -> Result<DkgOutput<P>, BlsError> {
+        let qualified = self.vss.qualified();
+        if qualified.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        let ones = vec![Fr::one(); qualified.len()];
+        let (mut value, coeffs) = self.vss.combine(&qualified[..], &ones[..])?;
+        let ret = DkgOutput::new(self.index, value, coeffs, qualified);
+        value.zeroize();
+        ret
+    }
+}
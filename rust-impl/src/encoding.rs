@@ -0,0 +1,95 @@
+/*!
+Fixed-size byte encodings of secret keys, public keys, and signatures
+
+Points use the ZCash BLS12-381 serialization format: the three most significant
+bits of the first byte are the compression, infinity, and sign flags, respectively.
+*/
+
+use error::BlsError;
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing_plus::bls12_381::{
+    Fr, FrRepr, G1Compressed, G1Uncompressed, G2Compressed, G2Uncompressed, G1, G2,
+};
+use pairing_plus::{CurveAffine, CurveProjective, EncodedPoint};
+
+/// Fixed-size compressed and uncompressed encodings of a group element
+pub trait PointEncoding: CurveProjective {
+    /// Compressed encoding: 48 bytes for G1, 96 bytes for G2
+    type Compressed: AsRef<[u8]> + AsMut<[u8]> + Copy;
+
+    /// Uncompressed encoding: 96 bytes for G1, 192 bytes for G2
+    type Uncompressed: AsRef<[u8]> + AsMut<[u8]> + Copy;
+
+    /// Serialize in compressed form
+    fn to_compressed(&self) -> Self::Compressed;
+
+    /// Serialize in uncompressed form
+    fn to_uncompressed(&self) -> Self::Uncompressed;
+
+    /// Deserialize from compressed form.
+    ///
+    /// Fails if the flag bits are inconsistent, if the x-coordinate is not a canonical
+    /// field element, or if the point is not on the curve or not in the prime-order
+    /// subgroup. The identity element is accepted; see `BLSSigCore::key_validate`.
+    fn from_compressed(bytes: &Self::Compressed) -> Result<Self, BlsError>;
+
+    /// Deserialize from uncompressed form, with the same checks as `from_compressed`
+    fn from_uncompressed(bytes: &Self::Uncompressed) -> Result<Self, BlsError>;
+}
+
+macro_rules! point_encoding_impl {
+    ($projective:ident, $compressed:ident, $uncompressed:ident, $clen:expr, $ulen:expr) => {
+        impl PointEncoding for $projective {
+            type Compressed = [u8; $clen];
+            type Uncompressed = [u8; $ulen];
+
+            fn to_compressed(&self) -> [u8; $clen] {
+                let mut ret = [0u8; $clen];
+                ret.copy_from_slice($compressed::from_affine(self.into_affine()).as_ref());
+                ret
+            }
+
+            fn to_uncompressed(&self) -> [u8; $ulen] {
+                let mut ret = [0u8; $ulen];
+                ret.copy_from_slice($uncompressed::from_affine(self.into_affine()).as_ref());
+                ret
+            }
+
+            fn from_compressed(bytes: &[u8; $clen]) -> Result<Self, BlsError> {
+                let mut enc = $compressed::empty();
+                enc.as_mut().copy_from_slice(&bytes[..]);
+                Ok(enc.into_affine()?.into_projective())
+            }
+
+            fn from_uncompressed(bytes: &[u8; $ulen]) -> Result<Self, BlsError> {
+                let mut enc = $uncompressed::empty();
+                enc.as_mut().copy_from_slice(&bytes[..]);
+                Ok(enc.into_affine()?.into_projective())
+            }
+        }
+    };
+}
+
+point_encoding_impl!(G1, G1Compressed, G1Uncompressed, 48, 96);
+point_encoding_impl!(G2, G2Compressed, G2Uncompressed, 96, 192);
+
+/// Serialize a secret exponent as a 32-byte big-endian integer
+pub fn sk_to_bytes(x_prime: &Fr) -> [u8; 32] {
+    let mut ret = [0u8; 32];
+    // writing 32 bytes into a 32-byte buffer cannot fail
+    x_prime.into_repr().write_be(&mut ret[..]).unwrap();
+    ret
+}
+
+/// Deserialize a secret exponent from a 32-byte big-endian integer.
+/// Fails unless the value is in the range [1, r - 1].
+pub fn sk_from_bytes(bytes: &[u8; 32]) -> Result<Fr, BlsError> {
+    let mut repr = FrRepr::default();
+    // reading 32 bytes from a 32-byte buffer cannot fail
+    repr.read_be(&bytes[..]).unwrap();
+    let ret = Fr::from_repr(repr).map_err(|_| BlsError::InvalidEncoding)?;
+    if ret.is_zero() {
+        return Err(BlsError::InvalidEncoding);
+    }
+    Ok(ret)
+}
@@ -0,0 +1,93 @@
+/*!
+Errors for BLS signature operations
+*/
+
+use pairing_plus::GroupDecodingError;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Reasons that a BLS operation can fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlsError {
+    /// A key, signature, or scalar could not be decoded
+    InvalidEncoding,
+    /// A point is not in the prime-order subgroup
+    NotInSubgroup,
+    /// A point is the identity element
+    IdentityPoint,
+    /// The number of public keys does not match the number of messages
+    LengthMismatch,
+    /// Aggregate verification was given no public keys or messages
+    EmptyInput,
+    /// Aggregate verification was given the same message more than once
+    DuplicateMessage,
+    /// The pairing product was not the identity, i.e., the signature is invalid
+    PairingCheckFailed,
+    /// HKDF could not produce the requested output
+    KeyDerivation,
+    /// KeyGen was given fewer than 32 bytes of input keying material
+    IkmTooShort,
+    /// The total weight of the signers is below the required threshold
+    InsufficientWeight,
+    /// A signer index is outside the committee
+    InvalidSignerIndex,
+    /// The same signer was included more than once
+    DuplicateSigner,
+    /// A threshold is zero or exceeds the number of shares
+    InvalidThreshold,
+    /// A secret share does not match its dealer's commitments
+    InvalidShare,
+    /// A commitment has the wrong number of coefficients
+    InvalidCommitment,
+    /// A committee has 2^32 or more members
+    CommitteeTooLarge,
+}
+
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            BlsError::InvalidEncoding => "invalid encoding",
+            BlsError::NotInSubgroup => "point is not in the prime-order subgroup",
+            BlsError::IdentityPoint => "point is the identity",
+            BlsError::LengthMismatch => "number of public keys and messages differ",
+            BlsError::EmptyInput => "no public keys or messages were supplied",
+            BlsError::DuplicateMessage => "messages are not distinct",
+            BlsError::PairingCheckFailed => "pairing check failed",
+            BlsError::KeyDerivation => "key derivation failed",
+            BlsError::IkmTooShort => "input keying material is shorter than 32 bytes",
+            BlsError::InsufficientWeight => "total signer weight is below the threshold",
+            BlsError::InvalidSignerIndex => "signer index is outside the committee",
+            BlsError::DuplicateSigner => "signer was included more than once",
+            BlsError::InvalidThreshold => "threshold is zero or exceeds the number of shares",
+            BlsError::InvalidShare => "share does not match the dealer's commitments",
+            BlsError::InvalidCommitment => "commitment has the wrong number of coefficients",
+            BlsError::CommitteeTooLarge => "committee has 2^32 or more members",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for BlsError {}
+
+impl From<GroupDecodingError> for BlsError {
+    fn from(err: GroupDecodingError) -> Self {
+        match err {
+            GroupDecodingError::NotInSubgroup => BlsError::NotInSubgroup,
+            _ => BlsError::InvalidEncoding,
+        }
+    }
+}
+
+impl From<io::Error> for BlsError {
+    // SerDes wraps GroupDecodingError in an io::Error; unwrap it if we can
+    fn from(err: io::Error) -> Self {
+        match err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<GroupDecodingError>())
+        {
+            Some(GroupDecodingError::NotInSubgroup) => BlsError::NotInSubgroup,
+            _ => BlsError::InvalidEncoding,
+        }
+    }
+}
@@ -0,0 +1,246 @@
+/*!
+Messages hashed to the curve ahead of time
+
+Hashing a message to the curve and preparing the result for the pairing is a large part
+of the cost of signing and verifying. A `HashedMessage` holds that work, so it can be
+done once and reused, e.g., when many signers sign the same message or when a verifier
+sees the same message repeatedly. A `HashCache` keeps a bounded number of these around,
+evicting the least recently used entry when full.
+*/
+
+use ciphersuite::{_augment, Ciphersuite, PkT, SigT};
+use pairing_plus::{CurveAffine, CurveProjective};
+use signature::BLSSigCore;
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use typed::{Aug, Basic, Pop};
+
+/// Alias for the prepared form of a projective point
+type PrepT<G> = <<G as CurveProjective>::Affine as CurveAffine>::Prepared;
+
+/// Schemes that hash the message alone: `Basic` and `Pop`.
+/// Under `Aug` the hash also depends on the signer's public key; see `HashedMessage::new_aug`.
+pub trait Unaugmented: 'static {}
+
+impl Unaugmented for Basic {}
+
+impl Unaugmented for Pop {}
+
+/// A message hashed to the signature group `G` of ciphersuite `C`, along with the
+/// prepared form of the resulting point that the pairing consumes
+pub struct HashedMessage<G: CurveProjective, C: Ciphersuite<Group = G>> {
+    point: G,
+    prepared: PrepT<G>,
+    _suite: PhantomData<C>,
+}
+
+impl<C: Ciphersuite> HashedMessage<SigT<C>, C> {
+    /// Hash `msg` under `C::DST`. For `Aug` suites, use `new_aug` instead.
+    pub fn new<B: AsRef<[u8]>>(msg: B) -> Self
+    where
+        C::Scheme: Unaugmented,
+    {
+        Self::from_point(<SigT<C> as BLSSigCore<C::Expand>>::hash_to_point(
+            msg,
+            C::DST,
+        ))
+    }
+
+    /// Hash `pk || msg` under `C::DST`, as the message augmentation scheme does.
+    /// The result is only useful for signing with, or verifying under, `pk`.
+    pub fn new_aug<B: AsRef<[u8]>>(pk: &PkT<C>, msg: B) -> Self
+    where
+        C: Ciphersuite<Scheme = Aug>,
+    {
+        Self::new_raw(_augment(pk, msg))
+    }
+
+    // hash exactly these bytes, with no augmentation
+    fn new_raw<B: AsRef<[u8]>>(bytes: B) -> Self {
+        Self::from_point(<SigT<C> as BLSSigCore<C::Expand>>::hash_to_point(
+            bytes,
+            C::DST,
+        ))
+    }
+
+    fn from_point(point: SigT<C>) -> Self {
+        HashedMessage {
+            point,
+            prepared: point.into_affine().prepare(),
+            _suite: PhantomData,
+        }
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> HashedMessage<G, C> {
+    /// The hash of the message, a point in the signature group
+    pub fn as_point(&self) -> &G {
+        &self.point
+    }
+
+    /// The hash of the message, prepared for the pairing
+    pub fn as_prepared(&self) -> &PrepT<G> {
+        &self.prepared
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> Clone for HashedMessage<G, C> {
+    fn clone(&self) -> Self {
+        HashedMessage {
+            point: self.point,
+            prepared: self.prepared.clone(),
+            _suite: PhantomData,
+        }
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> PartialEq for HashedMessage<G, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> Eq for HashedMessage<G, C> {}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> fmt::Debug for HashedMessage<G, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("HashedMessage").field(&self.point).finish()
+    }
+}
+
+// a cache entry: the point, its prepared form, and the tick of its most recent use
+struct CacheEntry<G: CurveProjective> {
+    point: G,
+    prepared: PrepT<G>,
+    tick: u64,
+}
+
+// a cache key: the ciphersuite's expand_message variant, its DST, and the hashed bytes
+type CacheKey = (TypeId, &'static [u8], Vec<u8>);
+
+/// A bounded least-recently-used cache of messages hashed to the group `G`, keyed by
+/// (expand_message variant, DST, msg). One cache can serve every ciphersuite with
+/// signatures in `G`.
+pub struct HashCache<G: CurveProjective> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<CacheKey, CacheEntry<G>>,
+    // tick of last use -> key, so that the first entry is the least recently used
+    order: BTreeMap<u64, CacheKey>,
+}
+
+impl<G: CurveProjective> HashCache<G> {
+    /// A cache holding at most `capacity` hashed messages. A capacity of zero disables
+    /// caching, so every lookup hashes afresh.
+    pub fn new(capacity: usize) -> Self {
+        HashCache {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    /// The maximum number of hashed messages held at once
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of hashed messages currently held
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop every cached entry
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Whether the hash of `msg` under `C` is cached. This does not count as a use.
+    pub fn contains<C, B>(&self, msg: B) -> bool
+    where
+        C: Ciphersuite<Group = G>,
+        B: AsRef<[u8]>,
+    {
+        self.entries
+            .contains_key(&_cache_key::<C>(msg.as_ref().to_vec()))
+    }
+
+    /// Hash `msg` under `C::DST`, reusing a cached result if there is one.
+    /// For `Aug` suites, use `hashed_aug` instead.
+    pub fn hashed<C, B>(&mut self, msg: B) -> HashedMessage<G, C>
+    where
+        C: Ciphersuite<Group = G>,
+        C::Scheme: Unaugmented,
+        B: AsRef<[u8]>,
+    {
+        self.lookup::<C>(msg.as_ref().to_vec())
+    }
+
+    /// Hash `pk || msg` under `C::DST`, reusing a cached result if there is one
+    pub fn hashed_aug<C, B>(&mut self, pk: &PkT<C>, msg: B) -> HashedMessage<G, C>
+    where
+        C: Ciphersuite<Group = G, Scheme = Aug>,
+        G: BLSSigCore<C::Expand>,
+        B: AsRef<[u8]>,
+    {
+        self.lookup::<C>(_augment(pk, msg))
+    }
+
+    fn lookup<C: Ciphersuite<Group = G>>(&mut self, bytes: Vec<u8>) -> HashedMessage<G, C> {
+        self.tick += 1;
+        let key = _cache_key::<C>(bytes);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            self.order.remove(&entry.tick);
+            entry.tick = self.tick;
+            self.order.insert(self.tick, key);
+            return HashedMessage {
+                point: entry.point,
+                prepared: entry.prepared.clone(),
+                _suite: PhantomData,
+            };
+        }
+
+        let ret = HashedMessage::<SigT<C>, C>::new_raw(&key.2[..]);
+        if self.capacity == 0 {
+            return ret;
+        }
+        if self.entries.len() >= self.capacity {
+            let oldest = *self.order.keys().next().unwrap();
+            let evicted = self.order.remove(&oldest).unwrap();
+            self.entries.remove(&evicted);
+        }
+        self.entries.insert(
+            key.clone(),
+            CacheEntry {
+                point: ret.point,
+                prepared: ret.prepared.clone(),
+                tick: self.tick,
+            },
+        );
+        self.order.insert(self.tick, key);
+        ret
+    }
+}
+
+impl<G: CurveProjective> fmt::Debug for HashCache<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HashCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.entries.len())
+            .finish()
+    }
+}
+
+// two suites share entries only if they hash identically: same expander and same DST
+fn _cache_key<C: Ciphersuite>(bytes: Vec<u8>) -> CacheKey {
+    (TypeId::of::<C::Expand>(), C::DST, bytes)
+}
@@ -1,5 +1,5 @@
-#![cfg_attr(feature = "cargo-clippy", deny(warnings))]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::unreadable_literal))]
+#![cfg_attr(clippy, deny(warnings))]
+#![allow(clippy::unreadable_literal)]
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 
@@ -14,17 +14,66 @@
 extern crate byteorder;
 extern crate ff_zeroize as ff;
 
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
 #[cfg(test)]
 extern crate hex_literal;
 extern crate hkdf;
 extern crate pairing_plus;
-#[cfg(test)]
 extern crate rand;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 extern crate sha2;
+extern crate sha3;
+extern crate zeroize;
 
+mod aggregator;
+mod bitfield;
+#[macro_use]
+mod ciphersuite;
+mod committee;
+mod coordinator;
+mod dkg;
+mod encoding;
+mod error;
+mod hashed;
+mod prepared;
+mod reshare;
+#[cfg(feature = "serde")]
+mod serialization;
 mod signature;
+mod threshold;
+mod typed;
+mod vrf;
 
-pub use signature::{BLSSigCore, BLSSignatureAug, BLSSignatureBasic, BLSSignaturePop};
+pub use aggregator::Aggregator;
+pub use bitfield::BitfieldAggregate;
+pub use ciphersuite::{
+    Ciphersuite, G1XmdSha256Aug, G1XmdSha256Basic, G1XmdSha256Pop, G1XofShake256Aug,
+    G1XofShake256Basic, G1XofShake256Pop, G2XmdSha256Aug, G2XmdSha256Basic, G2XmdSha256Pop,
+    G2XofShake256Aug, G2XofShake256Basic, G2XofShake256Pop, PkT, PopCiphersuite, SchemeKind, SigT,
+};
+pub use committee::Committee;
+pub use coordinator::{MisbehaviorReport, SigningStatus, ThresholdCoordinator};
+pub use dkg::{DkgCommitment, DkgComplaint, DkgOutput, DkgParticipant, DkgShare};
+pub use encoding::{sk_from_bytes, sk_to_bytes, PointEncoding};
+pub use error::BlsError;
+pub use hashed::{HashCache, HashedMessage, Unaugmented};
+pub use prepared::PreparedPublicKey;
+pub use reshare::{ReshareDealer, ReshareRecipient, ShareRefresh};
+pub use signature::{
+    key_gen, try_xprime_from_sk, weighted_aggregate, xprime_from_sk, BLSSigCore, BLSSignatureAug,
+    BLSSignatureBasic, BLSSignatureBdn, BLSSignaturePop, KeyGenMode,
+};
+pub use threshold::{
+    split_secret, BLSSignatureThreshold, PartialSignature, PublicKeyShare, SecretShare,
+};
+pub use typed::{Aug, Basic, Pop, PublicKey, Scheme, SecretKey, Signature};
+pub use vrf::BLSVrf;
 
 #[cfg(test)]
 mod test;
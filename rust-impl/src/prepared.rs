@@ -0,0 +1,135 @@
+/*!
+Public keys prepared ahead of time
+
+Every verification converts the public key to affine form and prepares it for the
+pairing. For signatures in G1 the key lives in G2, where preparing means precomputing
+the line functions of the Miller loop, so this is a large part of the cost. A
+`PreparedPublicKey` does that work once, for keys that verify many signatures, e.g., a
+stable validator set.
+*/
+
+use ciphersuite::{Ciphersuite, PkT, SigT};
+use error::BlsError;
+use hashed::HashedMessage;
+use pairing_plus::{CurveAffine, CurveProjective};
+use signature::BLSSigCore;
+use std::fmt;
+use std::marker::PhantomData;
+use typed::{PublicKey, Scheme, Signature};
+
+/// Alias for the affine form of a projective point
+type AffineT<G> = <G as CurveProjective>::Affine;
+
+/// Alias for the prepared form of a projective point
+type PrepT<G> = <<G as CurveProjective>::Affine as CurveAffine>::Prepared;
+
+/// A public key for ciphersuite `C`, whose signatures live in `G`, stored in projective,
+/// affine, and prepared form. The key is checked with `key_validate` when it is prepared,
+/// so verifying with it never repeats that check.
+pub struct PreparedPublicKey<G: CurveProjective, C: Ciphersuite<Group = G>> {
+    point: PkT<C>,
+    affine: AffineT<PkT<C>>,
+    prepared: PrepT<PkT<C>>,
+    _suite: PhantomData<C>,
+}
+
+impl<C: Ciphersuite> PreparedPublicKey<SigT<C>, C> {
+    /// Validate and prepare a public key
+    pub fn new(pk: PkT<C>) -> Result<Self, BlsError> {
+        <SigT<C> as BLSSigCore<C::Expand>>::key_validate(&pk)?;
+        let affine = pk.into_affine();
+        Ok(PreparedPublicKey {
+            point: pk,
+            affine,
+            prepared: affine.prepare(),
+            _suite: PhantomData,
+        })
+    }
+
+    /// Validate and prepare a typed public key
+    pub fn from_public_key<S: Scheme<SigT<C>, Suite = C>>(
+        pk: &PublicKey<SigT<C>, S>,
+    ) -> Result<Self, BlsError> {
+        Self::new(*pk.as_point())
+    }
+
+    /// Verify a signature on a message under this public key
+    pub fn verify<S, B>(&self, sig: &Signature<SigT<C>, S>, msg: B) -> bool
+    where
+        S: Scheme<SigT<C>, Suite = C>,
+        B: AsRef<[u8]>,
+    {
+        self.try_verify(sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    pub fn try_verify<S, B>(&self, sig: &Signature<SigT<C>, S>, msg: B) -> Result<(), BlsError>
+    where
+        S: Scheme<SigT<C>, Suite = C>,
+        B: AsRef<[u8]>,
+    {
+        C::try_verify_prepared(self, *sig.as_point(), msg)
+    }
+
+    /// Verify a signature on a message that was already hashed
+    pub fn verify_hashed<S: Scheme<SigT<C>, Suite = C>>(
+        &self,
+        sig: &Signature<SigT<C>, S>,
+        hashed: &HashedMessage<SigT<C>, C>,
+    ) -> bool {
+        self.try_verify_hashed(sig, hashed).is_ok()
+    }
+
+    /// Like `verify_hashed`, but reports why verification failed
+    pub fn try_verify_hashed<S: Scheme<SigT<C>, Suite = C>>(
+        &self,
+        sig: &Signature<SigT<C>, S>,
+        hashed: &HashedMessage<SigT<C>, C>,
+    ) -> Result<(), BlsError> {
+        C::try_verify_hashed_prepared(self, *sig.as_point(), hashed)
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> PreparedPublicKey<G, C> {
+    /// The public key in projective form
+    pub fn as_point(&self) -> &PkT<C> {
+        &self.point
+    }
+
+    /// The public key in affine form
+    pub fn as_affine(&self) -> &AffineT<PkT<C>> {
+        &self.affine
+    }
+
+    /// The public key in prepared form
+    pub fn as_prepared(&self) -> &PrepT<PkT<C>> {
+        &self.prepared
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> Clone for PreparedPublicKey<G, C> {
+    fn clone(&self) -> Self {
+        PreparedPublicKey {
+            point: self.point,
+            affine: self.affine,
+            prepared: self.prepared.clone(),
+            _suite: PhantomData,
+        }
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> PartialEq for PreparedPublicKey<G, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> Eq for PreparedPublicKey<G, C> {}
+
+impl<G: CurveProjective, C: Ciphersuite<Group = G>> fmt::Debug for PreparedPublicKey<G, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PreparedPublicKey")
+            .field(&self.point)
+            .finish()
+    }
+}
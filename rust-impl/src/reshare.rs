@@ -0,0 +1,278 @@
+/*!
+Proactive refresh of threshold keys, and resharing to a new committee
+
+Both protocols run the same rounds as the DKG, with the same messages: commitments and
+private shares, then complaints, then answers. They differ in what the dealers share.
+
+In a refresh (Herzberg et al., Crypto '95), every member of the committee deals a random
+polynomial with constant term zero, so its commitment to the constant term must be the
+identity. Each member adds the shares it receives to its old share. The shared secret, and
+hence the group public key, does not change, but old shares are useless in combination with
+new ones, so an adversary must compromise `threshold` members between two refreshes.
+
+In a reshare (Desmedt and Jajodia, 1997), each member i of the old committee deals a random
+polynomial of the new degree whose constant term is its old share, so its commitment to
+the constant term must be its old public key share. At least the old threshold of old
+members must qualify; each member j of the new committee then interpolates its new share
+from the shares it received, exactly as `combine` interpolates signatures. Again the group
+public key does not change.
+*/
+
+use dkg::{
+    _check_index, _commitment_eval, _deal_help, _responses_help, DkgCommitment, DkgComplaint,
+    DkgOutput, DkgShare, VssState,
+};
+use error::BlsError;
+use ff::Field;
+use pairing_plus::bls12_381::Fr;
+use pairing_plus::CurveProjective;
+use rand::Rng;
+use std::collections::BTreeSet;
+use threshold::{_lagrange_at_zero, SecretShare};
+use zeroize::Zeroize;
+
+/// One member's view of a refresh of the shares of a key among members 1 through n
+#[derive(Debug)]
+pub struct ShareRefresh<P: CurveProjective> {
+    share: SecretShare,
+    coeffs: Vec<P>,
+    n: usize,
+    dealt: Vec<DkgShare>,
+    vss: VssState<P>,
+}
+
+impl<P: CurveProjective<Scalar = Fr>> ShareRefresh<P> {
+    /// Refresh the share in `old` among `n` members, with the same threshold.
+    /// Fails unless the threshold is at most `n` and the share's index is at most `n`.
+    pub fn new(old: &DkgOutput<P>, n: usize) -> Result<Self, BlsError> {
+        let share = old.secret_share().clone();
+        let coeffs = old.commitments().to_vec();
+        let constants = vec![P::zero(); n];
+        let vss = VssState::new(share.index(), coeffs.len(), n, n, Some(constants))?;
+        Ok(ShareRefresh {
+            share,
+            coeffs,
+            n,
+            dealt: Vec::new(),
+            vss,
+        })
+    }
+
+    /// The index of this member
+    pub fn index(&self) -> u32 {
+        self.share.index()
+    }
+
+    /// Round 1: deal a random polynomial with constant term zero. Broadcast the commitment,
+    /// and send share j to member j, including this member's own share.
+    pub fn deal<R: Rng>(&mut self, rng: &mut R) -> (DkgCommitment<P>, Vec<DkgShare>) {
+        let (commitment, shares) =
+            _deal_help(self.index(), Fr::zero(), self.coeffs.len(), self.n, rng);
+        self.dealt = shares.clone();
+        (commitment, shares)
+    }
+
+    /// Round 1: accept a member's broadcast commitment. Fails as
+    /// `DkgParticipant::receive_commitment` does, and also with `InvalidCommitment` if
+    /// the commitment to the constant term is not the identity.
+    pub fn receive_commitment(&mut self, commitment: DkgCommitment<P>) -> Result<(), BlsError> {
+        self.vss.receive_commitment(commitment)
+    }
+
+    /// Round 1: accept a share sent privately to this member, as
+    /// `DkgParticipant::receive_share` does
+    pub fn receive_share(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        self.vss.receive_share(share)
+    }
+
+    /// Round 2: complaints to broadcast, as `DkgParticipant::complaints`
+    pub fn complaints(&self) -> Vec<DkgComplaint> {
+        self.vss.complaints()
+    }
+
+    /// Round 2: record a broadcast complaint, as `DkgParticipant::receive_complaint` does
+    pub fn receive_complaint(&mut self, complaint: DkgComplaint) -> Result<(), BlsError> {
+        self.vss.receive_complaint(complaint)
+    }
+
+    /// Round 3: shares to broadcast, answering the open complaints against this member
+    pub fn responses(&self) -> Vec<DkgShare> {
+        _responses_help(self.vss.complainers(self.index()), &self.dealt[..])
+    }
+
+    /// Round 3: check a member's broadcast answer to a complaint, as
+    /// `DkgParticipant::receive_response` does
+    pub fn receive_response(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        self.vss.receive_response(share)
+    }
+
+    /// Finish the refresh: add the qualified members' shares to the old share, and their
+    /// commitments to the old commitments. Fails with `EmptyInput` if no member qualified.
+    pub fn finish(self) -> Result<DkgOutput<P>, BlsError> {
+        let qualified = self.vss.qualified();
+        if qualified.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        let ones = vec![Fr::one(); qualified.len()];
+        let (mut value, mut coeffs) = self.vss.combine(&qualified[..], &ones[..])?;
+        value.add_assign(self.share.value());
+        for (c, old) in coeffs.iter_mut().zip(&self.coeffs) {
+            c.add_assign(old);
+        }
+        let ret = DkgOutput::new(self.index(), value, coeffs, qualified);
+        value.zeroize();
+        ret
+    }
+}
+
+/// A member of the old committee in a reshare: deals its share to the new committee
+#[derive(Debug)]
+pub struct ReshareDealer {
+    share: SecretShare,
+    threshold: usize,
+    n: usize,
+    dealt: Vec<DkgShare>,
+    complainers: BTreeSet<u32>,
+}
+
+impl ReshareDealer {
+    /// Reshare `share` among a new committee of `n` members, any `threshold` of whom can
+    /// sign. Fails unless 1 <= `threshold` <= `n` < 2^32.
+    pub fn new(share: &SecretShare, threshold: usize, n: usize) -> Result<Self, BlsError> {
+        if threshold == 0 || threshold > n || n > u32::MAX as usize {
+            return Err(BlsError::InvalidThreshold);
+        }
+        Ok(ReshareDealer {
+            share: share.clone(),
+            threshold,
+            n,
+            dealt: Vec::new(),
+            complainers: BTreeSet::new(),
+        })
+    }
+
+    /// The index of this member in the old committee
+    pub fn index(&self) -> u32 {
+        self.share.index()
+    }
+
+    /// Round 1: deal a random polynomial whose constant term is the old share. Broadcast
+    /// the commitment, and send share j to new member j.
+    pub fn deal<P: CurveProjective<Scalar = Fr>, R: Rng>(
+        &mut self,
+        rng: &mut R,
+    ) -> (DkgCommitment<P>, Vec<DkgShare>) {
+        let (commitment, shares) = _deal_help(
+            self.index(),
+            *self.share.value(),
+            self.threshold,
+            self.n,
+            rng,
+        );
+        self.dealt = shares.clone();
+        (commitment, shares)
+    }
+
+    /// Round 2: record a broadcast complaint. Complaints against other dealers are ignored.
+    /// Fails if the complainer is outside the new committee or if the complaint is a repeat.
+    pub fn receive_complaint(&mut self, complaint: DkgComplaint) -> Result<(), BlsError> {
+        _check_index(complaint.complainer(), self.n)?;
+        if complaint.dealer() == self.index() && !self.complainers.insert(complaint.complainer()) {
+            return Err(BlsError::DuplicateSigner);
+        }
+        Ok(())
+    }
+
+    /// Round 3: shares to broadcast, answering the complaints against this member
+    pub fn responses(&self) -> Vec<DkgShare> {
+        _responses_help(Some(&self.complainers), &self.dealt[..])
+    }
+}
+
+/// A member of the new committee in a reshare: receives the old members' shares and
+/// interpolates its own
+#[derive(Debug)]
+pub struct ReshareRecipient<P: CurveProjective> {
+    index: u32,
+    old_threshold: usize,
+    vss: VssState<P>,
+}
+
+impl<P: CurveProjective<Scalar = Fr>> ReshareRecipient<P> {
+    /// Member `index` of a new committee of `n` members, any `threshold` of whom can sign,
+    /// receiving a key shared among `old_n` members with commitments `old_commitments`,
+    /// e.g., from `DkgOutput::commitments`. Fails unless 1 <= `threshold` <= `n` < 2^32,
+    /// 1 <= `index` <= `n`, and there are between 1 and `old_n` old commitments.
+    pub fn new(
+        index: u32,
+        threshold: usize,
+        n: usize,
+        old_commitments: &[P],
+        old_n: usize,
+    ) -> Result<Self, BlsError> {
+        if old_commitments.is_empty() {
+            return Err(BlsError::InvalidCommitment);
+        }
+        if old_commitments.len() > old_n || old_n > u32::MAX as usize {
+            return Err(BlsError::InvalidThreshold);
+        }
+        // each old member's commitment to its constant term is its old public key share
+        let constants = (1..=old_n as u32)
+            .map(|i| _commitment_eval(old_commitments, i))
+            .collect();
+        Ok(ReshareRecipient {
+            index,
+            old_threshold: old_commitments.len(),
+            vss: VssState::new(index, threshold, old_n, n, Some(constants))?,
+        })
+    }
+
+    /// The index of this member in the new committee
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Round 1: accept an old member's broadcast commitment. Fails as
+    /// `DkgParticipant::receive_commitment` does, and also with `InvalidCommitment` if
+    /// the commitment to the constant term is not the old member's public key share.
+    pub fn receive_commitment(&mut self, commitment: DkgCommitment<P>) -> Result<(), BlsError> {
+        self.vss.receive_commitment(commitment)
+    }
+
+    /// Round 1: accept a share sent privately to this member, as
+    /// `DkgParticipant::receive_share` does
+    pub fn receive_share(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        self.vss.receive_share(share)
+    }
+
+    /// Round 2: complaints to broadcast, as `DkgParticipant::complaints`
+    pub fn complaints(&self) -> Vec<DkgComplaint> {
+        self.vss.complaints()
+    }
+
+    /// Round 2: record a broadcast complaint, as `DkgParticipant::receive_complaint` does
+    pub fn receive_complaint(&mut self, complaint: DkgComplaint) -> Result<(), BlsError> {
+        self.vss.receive_complaint(complaint)
+    }
+
+    /// Round 3: check an old member's broadcast answer to a complaint, as
+    /// `DkgParticipant::receive_response` does
+    pub fn receive_response(&mut self, share: DkgShare) -> Result<(), BlsError> {
+        self.vss.receive_response(share)
+    }
+
+    /// Finish the reshare: interpolate the new share, and the new commitments, from those
+    /// of the qualified old members. Fails with `InvalidThreshold` if fewer than the old
+    /// threshold of old members qualified.
+    pub fn finish(self) -> Result<DkgOutput<P>, BlsError> {
+        let qualified = self.vss.qualified();
+        if qualified.len() < self.old_threshold {
+            return Err(BlsError::InvalidThreshold);
+        }
+        let lambdas = _lagrange_at_zero(&qualified[..])?;
+        let (mut value, coeffs) = self.vss.combine(&qualified[..], &lambdas[..])?;
+        let ret = DkgOutput::new(self.index, value, coeffs, qualified);
+        value.zeroize();
+        ret
+    }
+}
@@ -0,0 +1,264 @@
+/*!
+serde support for public keys, signatures, aggregators, bitfield certificates, DKG
+commitments and complaints, and (with the `serde-secret-keys` feature) secret keys and DKG
+shares
+
+Human-readable formats (e.g., JSON) get a lowercase hex string of the compressed encoding;
+binary formats (e.g., bincode) get the raw compressed bytes. Deserialization goes through
+the validating decoders, so a successfully deserialized public key passes `key_validate`
+and a successfully deserialized signature is in the prime-order subgroup.
+*/
+
+use aggregator::Aggregator;
+use bitfield::BitfieldAggregate;
+use ciphersuite::PopCiphersuite;
+use dkg::{DkgCommitment, DkgComplaint};
+use encoding::PointEncoding;
+use error::BlsError;
+use pairing_plus::bls12_381::Fr;
+use pairing_plus::serdes::SerDes;
+use pairing_plus::CurveProjective;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+use typed::{PublicKey, Scheme, Signature};
+#[cfg(feature = "serde-secret-keys")]
+use {dkg::DkgShare, typed::SecretKey, zeroize::Zeroize};
+
+fn _to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut ret = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        ret.push(DIGITS[(b >> 4) as usize] as char);
+        ret.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    ret
+}
+
+fn _from_hex(s: &str) -> Result<Vec<u8>, BlsError> {
+    fn nibble(c: u8) -> Result<u8, BlsError> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(BlsError::InvalidEncoding),
+        }
+    }
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(2) {
+        return Err(BlsError::InvalidEncoding);
+    }
+    s.chunks(2)
+        .map(|c| Ok((nibble(c[0])? << 4) | nibble(c[1])?))
+        .collect()
+}
+
+// hex string if human readable, raw bytes otherwise
+fn _ser_help<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&_to_hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+// visitor that hands the decoded bytes to a validating decoder
+struct BytesVisitor<T, F> {
+    decode: F,
+    expecting: &'static str,
+    _out: PhantomData<T>,
+}
+
+impl<'de, T, F> Visitor<'de> for BytesVisitor<T, F>
+where
+    F: FnOnce(&[u8]) -> Result<T, BlsError>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        let bytes = _from_hex(v).map_err(E::custom)?;
+        (self.decode)(&bytes[..]).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        (self.decode)(v).map_err(E::custom)
+    }
+}
+
+fn _deser_help<'de, D, T, F>(
+    deserializer: D,
+    decode: F,
+    expecting: &'static str,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(&[u8]) -> Result<T, BlsError>,
+{
+    let visitor = BytesVisitor {
+        decode,
+        expecting,
+        _out: PhantomData,
+    };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Serialize for PublicKey<G, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        _ser_help(self.to_compressed().as_ref(), serializer)
+    }
+}
+
+impl<'de, G: CurveProjective, S: Scheme<G>> Deserialize<'de> for PublicKey<G, S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            Self::from_bytes,
+            "a compressed BLS public key",
+        )
+    }
+}
+
+impl<G: PointEncoding + SerDes, S: Scheme<G>> Serialize for Signature<G, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        _ser_help(self.to_compressed().as_ref(), serializer)
+    }
+}
+
+impl<'de, G: PointEncoding + SerDes, S: Scheme<G>> Deserialize<'de> for Signature<G, S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(deserializer, Self::from_bytes, "a compressed BLS signature")
+    }
+}
+
+impl<G: PointEncoding> Serialize for Aggregator<G> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        _ser_help(&self.to_bytes()[..], serializer)
+    }
+}
+
+impl<'de, G: PointEncoding> Deserialize<'de> for Aggregator<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            Self::from_bytes,
+            "a member count and a compressed aggregate",
+        )
+    }
+}
+
+impl<C: PopCiphersuite> Serialize for BitfieldAggregate<C> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        _ser_help(&self.to_bytes()[..], serializer)
+    }
+}
+
+impl<'de, C: PopCiphersuite> Deserialize<'de> for BitfieldAggregate<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            Self::from_bytes,
+            "a committee size, a compressed signature, and a signer bitmap",
+        )
+    }
+}
+
+impl<P: PointEncoding + CurveProjective<Scalar = Fr>> Serialize for DkgCommitment<P> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        _ser_help(&self.to_bytes()[..], serializer)
+    }
+}
+
+impl<'de, P: PointEncoding + CurveProjective<Scalar = Fr>> Deserialize<'de> for DkgCommitment<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            Self::from_bytes,
+            "a dealer index and compressed commitments",
+        )
+    }
+}
+
+impl Serialize for DkgComplaint {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        _ser_help(&self.to_bytes()[..], serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DkgComplaint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            Self::from_bytes,
+            "a dealer index and a complainer index",
+        )
+    }
+}
+
+#[cfg(feature = "serde-secret-keys")]
+impl<G: CurveProjective<Scalar = Fr>, S: Scheme<G>> Serialize for SecretKey<G, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut bytes = self.to_bytes();
+        let ret = _ser_help(&bytes[..], serializer);
+        bytes.zeroize();
+        ret
+    }
+}
+
+#[cfg(feature = "serde-secret-keys")]
+impl<'de, G: CurveProjective<Scalar = Fr>, S: Scheme<G>> Deserialize<'de> for SecretKey<G, S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            |bytes| {
+                if bytes.len() != 32 {
+                    return Err(BlsError::InvalidEncoding);
+                }
+                let mut buf = [0u8; 32];
+                buf.copy_from_slice(bytes);
+                let ret = Self::from_bytes(&buf);
+                buf.zeroize();
+                ret
+            },
+            "a 32-byte big-endian BLS secret key",
+        )
+    }
+}
+
+#[cfg(feature = "serde-secret-keys")]
+impl Serialize for DkgShare {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut bytes = self.to_bytes();
+        let ret = _ser_help(&bytes[..], serializer);
+        bytes.zeroize();
+        ret
+    }
+}
+
+#[cfg(feature = "serde-secret-keys")]
+impl<'de> Deserialize<'de> for DkgShare {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        _deser_help(
+            deserializer,
+            |bytes| {
+                if bytes.len() != 40 {
+                    return Err(BlsError::InvalidEncoding);
+                }
+                let mut buf = [0u8; 40];
+                buf.copy_from_slice(bytes);
+                let ret = Self::from_bytes(&buf);
+                buf.zeroize();
+                ret
+            },
+            "two signer indices and a 32-byte big-endian share",
+        )
+    }
+}
@@ -2,38 +2,299 @@
 BLS signatures
 */
 
-use ff::Field;
+use encoding::PointEncoding;
+use error::BlsError;
+use ff::{Field, PrimeField};
 use hkdf::Hkdf;
-use pairing_plus::bls12_381::{Bls12, Fq12, Fr, G1, G2};
+use pairing_plus::bls12_381::{Bls12, Fq12, Fr, FrRepr, G1Affine, G2Affine, G1, G2};
 use pairing_plus::hash_to_curve::HashToCurve;
-use pairing_plus::hash_to_field::{BaseFromRO, ExpandMsg, ExpandMsgXmd};
+use pairing_plus::hash_to_field::{hash_to_field, BaseFromRO, ExpandMsg, ExpandMsgXmd};
 use pairing_plus::serdes::SerDes;
 use pairing_plus::{CurveAffine, CurveProjective, Engine};
+use rand::{thread_rng, Rng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use sha2::digest::generic_array::typenum::{U48, U96};
 use sha2::digest::generic_array::{ArrayLength, GenericArray};
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use std::io::Cursor;
+use std::sync::OnceLock;
 use std::vec::Vec;
+use zeroize::Zeroize;
 
 /// Hash a secret key sk to the secret exponent x'; then (PK, SK) = (g^{x'}, x').
-// NOTE: this implementation leaves the key_info parameter as the default empty string
+// NOTE: this is the legacy KeyGen, kept so that existing keys still derive the same way;
+// key_gen(ikm, key_info, KeyGenMode::Spec) is the spec-compliant entry point
 pub fn xprime_from_sk<B: AsRef<[u8]>>(msg: B) -> Fr {
-    const SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
-    // copy of `msg` with appended zero byte
-    let mut msg_prime = Vec::<u8>::with_capacity(msg.as_ref().len() + 1);
-    msg_prime.extend_from_slice(msg.as_ref());
-    msg_prime.extend_from_slice(&[0]);
+    // HKDF-Expand only fails when asked for more than 255 * HashLen bytes
+    try_xprime_from_sk(msg).unwrap()
+}
+
+/// Like `xprime_from_sk`, but returns an error instead of panicking.
+pub fn try_xprime_from_sk<B: AsRef<[u8]>>(msg: B) -> Result<Fr, BlsError> {
+    key_gen(msg, [], KeyGenMode::Legacy)
+}
+
+/// Selects the procedure used by `key_gen`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyGenMode {
+    /// KeyGen from draft-irtf-cfrg-bls-signature-04: requires at least 32 bytes of IKM,
+    /// and re-hashes the salt until the resulting secret key is nonzero.
+    Spec,
+    /// The original KeyGen used by `xprime_from_sk`: a single HKDF invocation with
+    /// a fixed salt and no restriction on the length of IKM.
+    Legacy,
+}
+
+/// Derive a secret exponent from input keying material `ikm` and `key_info`.
+pub fn key_gen<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+    ikm: B,
+    key_info: C,
+    mode: KeyGenMode,
+) -> Result<Fr, BlsError> {
+    if mode == KeyGenMode::Spec && ikm.as_ref().len() < 32 {
+        return Err(BlsError::IkmTooShort);
+    }
+    // copy of `ikm` with appended zero byte
+    let mut ikm_prime = Vec::<u8>::with_capacity(ikm.as_ref().len() + 1);
+    ikm_prime.extend_from_slice(ikm.as_ref());
+    ikm_prime.extend_from_slice(&[0]);
+    // key_info || I2OSP(L, 2)
+    let mut info = Vec::<u8>::with_capacity(key_info.as_ref().len() + 2);
+    info.extend_from_slice(key_info.as_ref());
+    info.extend_from_slice(&[0, 48]);
     // `result` has enough length to hold the output from HKDF expansion
     let mut result = GenericArray::<u8, U48>::default();
-    assert!(Hkdf::<Sha256>::new(Some(SALT), &msg_prime[..])
-        .expand(&[0, 48], &mut result)
-        .is_ok());
-    Fr::from_okm(&result)
+
+    let ret = _key_gen_help(&ikm_prime[..], &info[..], &mut result, mode);
+    // both buffers hold secret material: IKM and OKM, respectively
+    ikm_prime.zeroize();
+    result.as_mut_slice().zeroize();
+    ret
+}
+
+// the HKDF part of key_gen; the caller is responsible for clearing ikm_prime and result
+fn _key_gen_help(
+    ikm_prime: &[u8],
+    info: &[u8],
+    result: &mut GenericArray<u8, U48>,
+    mode: KeyGenMode,
+) -> Result<Fr, BlsError> {
+    const SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+    if mode == KeyGenMode::Legacy {
+        Hkdf::<Sha256>::new(Some(SALT), ikm_prime)
+            .expand(info, result)
+            .map_err(|_| BlsError::KeyDerivation)?;
+        return Ok(Fr::from_okm(result));
+    }
+
+    let mut salt = Sha256::digest(SALT);
+    loop {
+        Hkdf::<Sha256>::new(Some(&salt[..]), ikm_prime)
+            .expand(info, result)
+            .map_err(|_| BlsError::KeyDerivation)?;
+        let x_prime = Fr::from_okm(result);
+        if !x_prime.is_zero() {
+            return Ok(x_prime);
+        }
+        salt = Sha256::digest(&salt[..]);
+    }
+}
+
+// serialize a public key in compressed form
+fn _pk_ser_help<T: SerDes>(pk: &T, buf: &mut [u8]) -> Result<(), BlsError> {
+    let mut cur = Cursor::new(buf);
+    pk.serialize(&mut cur, true)?;
+    Ok(())
+}
+
+// check the result of a multi-pairing
+fn _pairing_check(pairingproduct: Option<Fq12>) -> Result<(), BlsError> {
+    match pairingproduct {
+        Some(pp) if pp == Fq12::one() => Ok(()),
+        _ => Err(BlsError::PairingCheckFailed),
+    }
+}
+
+// subgroup check by multiplication by the group order
+fn _subgroup_check<T: CurveProjective>(pt: &T) -> Result<(), BlsError> {
+    if pt.into_affine().mul(ScalarT::<T>::char()).is_zero() {
+        Ok(())
+    } else {
+        Err(BlsError::NotInSubgroup)
+    }
+}
+
+// enforce that pks and msgs pair up one-to-one: used in all aggregate verify variants
+pub(crate) fn _len_check(npks: usize, nmsgs: usize) -> Result<(), BlsError> {
+    if npks != nmsgs {
+        return Err(BlsError::LengthMismatch);
+    }
+    if npks == 0 {
+        return Err(BlsError::EmptyInput);
+    }
+    Ok(())
+}
+
+// enforce that pks, msgs, and sigs line up one-to-one: used in all batch verify variants
+pub(crate) fn _batch_len_check(npks: usize, nmsgs: usize, nsigs: usize) -> Result<(), BlsError> {
+    _len_check(npks, nmsgs)?;
+    if nsigs != npks {
+        return Err(BlsError::LengthMismatch);
+    }
+    Ok(())
+}
+
+// nonzero 64-bit random scalar for randomized batch verification: a batch containing an
+// invalid signature passes with probability at most 2^-63 over the choice of scalars
+fn _batch_scalar<R: Rng>(rng: &mut R) -> FrRepr {
+    loop {
+        let r: u64 = rng.gen();
+        if r != 0 {
+            return FrRepr::from(r);
+        }
+    }
+}
+
+/// Alias for prepared G1 points
+type G1Prep = <G1Affine as CurveAffine>::Prepared;
+
+/// Alias for prepared G2 points
+type G2Prep = <G2Affine as CurveAffine>::Prepared;
+
+// -g1 and -g2 in prepared form: every verification pairs the signature with one of them
+static NEG_G1_PREP: OnceLock<G1Prep> = OnceLock::new();
+static NEG_G2_PREP: OnceLock<G2Prep> = OnceLock::new();
+
+// the negated generator of G1, prepared on first use
+fn _neg_g1_prep() -> &'static G1Prep {
+    NEG_G1_PREP.get_or_init(|| {
+        let mut g1gen = G1::one();
+        g1gen.negate();
+        g1gen.into_affine().prepare()
+    })
+}
+
+// the negated generator of G2, prepared on first use
+fn _neg_g2_prep() -> &'static G2Prep {
+    NEG_G2_PREP.get_or_init(|| {
+        let mut g2gen = G2::one();
+        g2gen.negate();
+        g2gen.into_affine().prepare()
+    })
+}
+
+// compute f(i) for each i in 0..n, on the rayon thread pool with the `parallel` feature
+#[cfg(feature = "parallel")]
+fn _map_help<T, F>(n: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Send + Sync,
+{
+    (0..n).into_par_iter().map(f).collect()
+}
+
+// compute f(i) for each i in 0..n
+#[cfg(not(feature = "parallel"))]
+fn _map_help<T, F>(n: usize, f: F) -> Vec<T>
+where
+    F: Fn(usize) -> T,
+{
+    (0..n).map(f).collect()
+}
+
+// Miller loop over all of `pairs`, split into one chunk per thread with the `parallel`
+// feature. The Miller loop of a set of pairs is the product of the Miller loops of any
+// partition of it, and Fq12 arithmetic is exact, so the result is bit-identical to the
+// serial version below.
+#[cfg(feature = "parallel")]
+pub(crate) fn _multi_miller_loop(pairs: &[(&G1Prep, &G2Prep)]) -> Fq12 {
+    let chunk_size = 1 + pairs.len() / rayon::current_num_threads();
+    pairs
+        .par_chunks(chunk_size)
+        .map(Bls12::miller_loop)
+        .reduce(Fq12::one, |mut acc, f| {
+            acc.mul_assign(&f);
+            acc
+        })
+}
+
+// Miller loop over all of `pairs`
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn _multi_miller_loop(pairs: &[(&G1Prep, &G2Prep)]) -> Fq12 {
+    Bls12::miller_loop(pairs)
+}
+
+// borrow each prepared pair, in the form that the Miller loop takes
+fn _pair_refs(pairs: &[(G1Prep, G2Prep)]) -> Vec<(&G1Prep, &G2Prep)> {
+    pairs.iter().map(|(p, q)| (p, q)).collect()
+}
+
+// the pairing check for a randomized batch: prod_i e(pairs_i) * e(sum_term) == 1
+fn _batch_check(
+    pairs: &[(&G1Prep, &G2Prep)],
+    sum_term: (&G1Prep, &G2Prep),
+) -> Result<(), BlsError> {
+    let f = _multi_miller_loop(pairs);
+    let mut f_sum = Bls12::miller_loop(&[sum_term]);
+    f_sum.mul_assign(&f);
+    _pairing_check(Bls12::final_exponentiation(&f_sum))
+}
+
+// the sum of the scaled signatures r_i * sig_i, prepared for the term that closes a batch
+fn _prep_sum<G: CurveProjective>(rsigs: &[G]) -> <G::Affine as CurveAffine>::Prepared {
+    _agg_help(rsigs).into_affine().prepare()
+}
+
+// recursive bisection over entries lo..hi, pushing the indices of the bad entries onto
+// `bad`. `check(lo, hi)` runs the batch check on one range, reusing the pairs prepared
+// by the caller. When `known_bad` is set, the caller already knows this range fails, so
+// we skip its check.
+fn _bisect<F>(check: &F, lo: usize, hi: usize, known_bad: bool, bad: &mut Vec<usize>)
+where
+    F: Fn(usize, usize) -> bool,
+{
+    if !known_bad && check(lo, hi) {
+        return;
+    }
+    if hi - lo == 1 {
+        bad.push(lo);
+        return;
+    }
+    let mid = lo + (hi - lo) / 2;
+    let nbad = bad.len();
+    _bisect(check, lo, mid, false, bad);
+    // if the left half is fine, the right half must be the one that failed
+    _bisect(check, mid, hi, bad.len() == nbad, bad);
+}
+
+// find the bad entries among the `n` triples that passed the per-entry checks
+fn _find_invalid_help<F>(n: usize, check: &F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut bad = Vec::new();
+    if n != 0 {
+        _bisect(check, 0, n, false, &mut bad);
+    }
+    bad
+}
+
+// enforce uniqueness of messages: used in Basic aggregate verify
+pub(crate) fn _unique_help<B: AsRef<[u8]>>(msgs: &[B]) -> Result<(), BlsError> {
+    let mut msg_set = HashSet::<&[u8]>::with_capacity(msgs.len());
+    for msg in msgs {
+        msg_set.insert(msg.as_ref());
+    }
+    if msg_set.len() != msgs.len() {
+        return Err(BlsError::DuplicateMessage);
+    }
+    Ok(())
 }
 
 // multi-point-addition helper: used in aggregate and in PoP verify
-fn _agg_help<T: CurveProjective>(ins: &[T]) -> T {
+pub(crate) fn _agg_help<T: CurveProjective>(ins: &[T]) -> T {
     let mut ret = T::zero();
     for inv in ins {
         ret.add_assign(inv);
@@ -41,20 +302,124 @@ fn _agg_help<T: CurveProjective>(ins: &[T]) -> T {
     ret
 }
 
+/// Compute sum_i weights[i] * points[i] using multi-scalar multiplication.
+/// Use this to combine signatures, or public keys, with integer weights such as stake.
+pub fn weighted_aggregate<T: CurveProjective>(
+    points: &[T],
+    weights: &[u64],
+) -> Result<T, BlsError> {
+    _len_check(points.len(), weights.len())?;
+    Ok(_weighted_agg_help(points, weights))
+}
+
+// weighted sum with integer weights, for slices of equal length
+fn _weighted_agg_help<T: CurveProjective>(points: &[T], weights: &[u64]) -> T {
+    let scalars: Vec<[u64; 4]> = weights.iter().map(|&w| [w, 0, 0, 0]).collect();
+    _msm_help(points, &scalars[..])
+}
+
+// multi-scalar multiplication with little-endian 256-bit scalars, for slices of equal length
+pub(crate) fn _msm_help<T: CurveProjective>(points: &[T], scalars: &[[u64; 4]]) -> T {
+    let mut affine: Vec<_> = points.to_vec();
+    T::batch_normalization(&mut affine[..]);
+    let affine: Vec<_> = affine.iter().map(|p| p.into_affine()).collect();
+    let scalars: Vec<&[u64; 4]> = scalars.iter().collect();
+    T::Affine::sum_of_products(&affine[..], &scalars[..])
+}
+
+// BDN coefficients for the public keys `pks`, all hashed under the tag `dst`:
+//     d = expand_message(pk_1 || ... || pk_n, 32)
+//     t_i = hash_to_field(pk_i || d) in Fr
+// with every public key in compressed form. Hashing the set once into d keeps this linear.
+pub(crate) fn _bdn_coefficients<X: ExpandMsg, P: PointEncoding>(pks: &[P], dst: &[u8]) -> Vec<Fr> {
+    let pks_bytes: Vec<_> = pks.iter().map(|pk| pk.to_compressed()).collect();
+    let mut all =
+        Vec::<u8>::with_capacity(pks_bytes.len() * P::zero().to_compressed().as_ref().len());
+    for pk_bytes in &pks_bytes {
+        all.extend_from_slice(pk_bytes.as_ref());
+    }
+    let digest = X::expand_message(&all[..], dst, 32);
+    pks_bytes
+        .iter()
+        .map(|pk_bytes| {
+            let mut msg = pk_bytes.as_ref().to_vec();
+            msg.extend_from_slice(&digest[..]);
+            hash_to_field::<Fr, X>(&msg[..], dst, 1)[0]
+        })
+        .collect()
+}
+
+// sum_i t_i * points[i], where t_i are the BDN coefficients of `pks`
+pub(crate) fn _bdn_agg_help<X, P, T>(pks: &[P], points: &[T], dst: &[u8]) -> T
+where
+    X: ExpandMsg,
+    P: PointEncoding,
+    T: CurveProjective,
+{
+    let scalars: Vec<[u64; 4]> = _bdn_coefficients::<X, P>(pks, dst)
+        .iter()
+        .map(|t| t.into_repr().0)
+        .collect();
+    _msm_help(points, &scalars[..])
+}
+
+// the weighted aggregate public key of `signers`, provided their total weight is at least
+// `min_weight`: used in weighted multisig verify
+pub(crate) fn _weighted_apk<T: CurveProjective>(
+    signers: &[(T, u64)],
+    min_weight: u64,
+) -> Result<T, BlsError> {
+    if signers.is_empty() {
+        return Err(BlsError::EmptyInput);
+    }
+    let total: u128 = signers.iter().map(|&(_, w)| u128::from(w)).sum();
+    if total < u128::from(min_weight) {
+        return Err(BlsError::InsufficientWeight);
+    }
+    let (pks, weights): (Vec<T>, Vec<u64>) = signers.iter().cloned().unzip();
+    let apk = _weighted_agg_help(&pks[..], &weights[..]);
+    if apk.is_zero() {
+        return Err(BlsError::IdentityPoint);
+    }
+    Ok(apk)
+}
+
 /// Alias for the scalar type corresponding to a CurveProjective type
 type ScalarT<PtT> = <PtT as CurveProjective>::Scalar;
 
+/// Alias for the prepared form of a CurveProjective type
+type PrepT<PtT> = <<PtT as CurveProjective>::Affine as CurveAffine>::Prepared;
+
 /// BLS signature implementation
-pub trait BLSSigCore<X: ExpandMsg>: CurveProjective {
+pub trait BLSSigCore<X: ExpandMsg>: CurveProjective<Scalar: Zeroize> {
     /// The type of the public key
     type PKType: CurveProjective<Engine = <Self as CurveProjective>::Engine, Scalar = ScalarT<Self>>
+        + PointEncoding
         + SerDes;
 
     /// Generate secret exponent and public key
     /// * input: the secret key as bytes
     /// * output: the actual secret key x_prime, a.k.a, the secret scala
     /// * output: the public key g^x_prime
-    fn keygen<B: AsRef<[u8]>>(sk: B) -> (ScalarT<Self>, Self::PKType);
+    fn keygen<B: AsRef<[u8]>>(sk: B) -> (ScalarT<Self>, Self::PKType) {
+        // HKDF-Expand only fails when asked for more than 255 * HashLen bytes
+        Self::try_keygen(sk).unwrap()
+    }
+
+    /// Like `keygen`, but returns an error instead of panicking
+    fn try_keygen<B: AsRef<[u8]>>(sk: B) -> Result<(ScalarT<Self>, Self::PKType), BlsError> {
+        Self::key_gen_with_info(sk, [], KeyGenMode::Legacy)
+    }
+
+    /// Generate secret exponent and public key using `key_gen`
+    /// * input: the input keying material
+    /// * input: the key_info string
+    /// * input: which KeyGen procedure to use
+    fn key_gen_with_info<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        ikm: B,
+        key_info: C,
+        mode: KeyGenMode,
+    ) -> Result<(ScalarT<Self>, Self::PKType), BlsError>;
 
     /// Sign a message
     /// * input: the actual secret key x_prime
@@ -78,7 +443,74 @@ pub trait BLSSigCore<X: ExpandMsg>: CurveProjective {
         sig: Self,
         msg: B,
         ciphersuite: C,
-    ) -> bool;
+    ) -> bool {
+        Self::try_core_verify(pk, sig, msg, ciphersuite).is_ok()
+    }
+
+    /// Like `core_verify`, but reports why verification failed
+    fn try_core_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pk: Self::PKType,
+        sig: Self,
+        msg: B,
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        Self::key_validate(&pk)?;
+        Self::try_core_verify_prevalidated(pk, sig, msg, ciphersuite)
+    }
+
+    /// Like `try_core_verify`, but skips `key_validate` on the public key.
+    /// Only use this for keys that were already validated, e.g., at registration time.
+    fn try_core_verify_prevalidated<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pk: Self::PKType,
+        sig: Self,
+        msg: B,
+        ciphersuite: C,
+    ) -> Result<(), BlsError>;
+
+    /// Hash a message to a point in the signature group
+    /// * input: the message as bytes
+    /// * input: the ciphersuite ID
+    /// * output: the point that `core_sign` multiplies by the secret exponent
+    fn hash_to_point<B: AsRef<[u8]>, C: AsRef<[u8]>>(msg: B, ciphersuite: C) -> Self;
+
+    /// Like `core_sign`, but takes the output of `hash_to_point` instead of the message
+    fn core_sign_hashed(mut x_prime: ScalarT<Self>, hashed: &Self) -> Self {
+        let mut ret = *hashed;
+        ret.mul_assign(x_prime);
+        x_prime.zeroize();
+        ret
+    }
+
+    /// Like `try_core_verify_prevalidated`, but takes the prepared output of
+    /// `hash_to_point` instead of the message and ciphersuite ID
+    fn try_core_verify_hashed_prevalidated(
+        pk: Self::PKType,
+        sig: Self,
+        hashed: &PrepT<Self>,
+    ) -> Result<(), BlsError> {
+        Self::try_core_verify_prepared(&pk.into_affine().prepare(), sig, hashed)
+    }
+
+    /// Like `try_core_verify_hashed_prevalidated`, but takes the public key in prepared
+    /// form, so that a key that verifies many signatures is only prepared once
+    fn try_core_verify_prepared(
+        pk: &PrepT<Self::PKType>,
+        sig: Self,
+        hashed: &PrepT<Self>,
+    ) -> Result<(), BlsError>;
+
+    /// KeyValidate: check that a public key is not the identity and is in the prime-order subgroup
+    fn key_validate(pk: &Self::PKType) -> Result<(), BlsError> {
+        if pk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        _subgroup_check(pk)
+    }
+
+    /// Check that a signature is in the prime-order subgroup
+    fn sig_subgroup_check(sig: &Self) -> Result<(), BlsError> {
+        _subgroup_check(sig)
+    }
 
     /// Aggregate signatures
     fn aggregate(sigs: &[Self]) -> Self {
@@ -91,7 +523,151 @@ pub trait BLSSigCore<X: ExpandMsg>: CurveProjective {
         msgs: &[B],
         sig: Self,
         ciphersuite: C,
-    ) -> bool;
+    ) -> bool {
+        Self::try_core_aggregate_verify(pks, msgs, sig, ciphersuite).is_ok()
+    }
+
+    /// Like `core_aggregate_verify`, but reports why verification failed.
+    /// `pks` and `msgs` must be nonempty and of equal length.
+    fn try_core_aggregate_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        for pk in pks {
+            Self::key_validate(pk)?;
+        }
+        Self::try_core_aggregate_verify_prevalidated(pks, msgs, sig, ciphersuite)
+    }
+
+    /// Like `try_core_aggregate_verify`, but skips `key_validate` on the public keys.
+    /// Only use this for keys that were already validated, e.g., at registration time.
+    fn try_core_aggregate_verify_prevalidated<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+        ciphersuite: C,
+    ) -> Result<(), BlsError>;
+
+    /// Like `try_core_aggregate_verify_prevalidated`, but takes the prepared outputs of
+    /// `hash_to_point` instead of the messages and ciphersuite ID
+    fn try_core_aggregate_verify_hashed_prevalidated(
+        pks: &[Self::PKType],
+        hashed: &[&PrepT<Self>],
+        sig: Self,
+    ) -> Result<(), BlsError>;
+
+    /// Like `try_core_aggregate_verify_prevalidated`, but takes the public keys in
+    /// prepared form
+    fn try_core_aggregate_verify_prepared<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pks: &[&PrepT<Self::PKType>],
+        msgs: &[B],
+        sig: Self,
+        ciphersuite: C,
+    ) -> Result<(), BlsError>;
+
+    /// Verify many independent (pk, msg, sig) triples at once.
+    ///
+    /// Each triple is weighted by a small random scalar drawn from `rng`, so the whole
+    /// batch costs one multi-Miller loop and one final exponentiation. The result agrees
+    /// with calling `core_verify` on every triple, except that a batch containing an
+    /// invalid signature is accepted with probability at most 2^-63.
+    fn core_batch_verify<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+        ciphersuite: C,
+    ) -> bool {
+        Self::try_core_batch_verify(rng, pks, msgs, sigs, ciphersuite).is_ok()
+    }
+
+    /// Like `core_batch_verify`, but reports why verification failed.
+    /// `pks`, `msgs`, and `sigs` must be nonempty and of equal length.
+    fn try_core_batch_verify<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        for pk in pks {
+            Self::key_validate(pk)?;
+        }
+        Self::try_core_batch_verify_prevalidated(rng, pks, msgs, sigs, ciphersuite)
+    }
+
+    /// Like `try_core_batch_verify`, but skips `key_validate` on the public keys.
+    /// Only use this for keys that were already validated, e.g., at registration time.
+    fn try_core_batch_verify_prevalidated<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+        ciphersuite: C,
+    ) -> Result<(), BlsError>;
+
+    /// Find the invalid entries among independent (pk, msg, sig) triples, returning
+    /// their indices in increasing order; an empty result means every triple is valid.
+    ///
+    /// Entries whose public key fails `key_validate` or whose signature is outside the
+    /// prime-order subgroup are reported directly. The rest are checked as one randomized
+    /// batch (see `core_batch_verify`); if that fails, the batch is split in half
+    /// recursively, reusing the hashed messages and prepared points at every level, so k
+    /// bad entries among n cost O(k log n) pairings rather than n. This is also the way to
+    /// find the culprits after `core_aggregate_verify` rejects an aggregate of `sigs`.
+    ///
+    /// Fails only if the lengths differ or the inputs are empty.
+    fn core_batch_find_invalid<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+        ciphersuite: C,
+    ) -> Result<Vec<usize>, BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let mut bad = Vec::new();
+        let mut good = Vec::with_capacity(pks.len());
+        for (i, pk) in pks.iter().enumerate() {
+            if Self::key_validate(pk).is_ok() {
+                good.push(i);
+            } else {
+                bad.push(i);
+            }
+        }
+        if good.is_empty() {
+            return Ok(bad);
+        }
+        let good_pks: Vec<_> = good.iter().map(|&i| pks[i]).collect();
+        let good_msgs: Vec<_> = good.iter().map(|&i| msgs[i].as_ref()).collect();
+        let good_sigs: Vec<_> = good.iter().map(|&i| sigs[i]).collect();
+        bad.extend(
+            Self::core_batch_find_invalid_prevalidated(
+                rng,
+                &good_pks,
+                &good_msgs,
+                &good_sigs,
+                ciphersuite,
+            )?
+            .into_iter()
+            .map(|j| good[j]),
+        );
+        bad.sort_unstable();
+        Ok(bad)
+    }
+
+    /// Like `core_batch_find_invalid`, but skips `key_validate` on the public keys.
+    /// Only use this for keys that were already validated, e.g., at registration time.
+    fn core_batch_find_invalid_prevalidated<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+        ciphersuite: C,
+    ) -> Result<Vec<usize>, BlsError>;
 }
 
 /// 'Basic' BLS signature
@@ -106,21 +682,120 @@ pub trait BLSSignatureBasic<X: ExpandMsg>: BLSSigCore<X> {
 
     /// re-export from BLSSigCore
     fn verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> bool {
-        <Self as BLSSigCore<X>>::core_verify(pk, sig, msg, Self::CSUITE)
+        Self::try_verify(pk, sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    fn try_verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify(pk, sig, msg, Self::CSUITE)
+    }
+
+    /// Like `try_verify`, but skips `key_validate` on the public key
+    fn try_verify_prevalidated<B: AsRef<[u8]>>(
+        pk: Self::PKType,
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(pk, sig, msg, Self::CSUITE)
     }
 
     /// check for uniqueness of msgs, then invoke verify from BLSSigCore
     fn aggregate_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], msgs: &[B], sig: Self) -> bool {
-        // enforce uniqueness of messages
-        let mut msg_set = HashSet::<&[u8]>::with_capacity(msgs.len());
-        for msg in msgs {
-            msg_set.insert(msg.as_ref());
-        }
-        if msg_set.len() != msgs.len() {
-            return false;
-        }
+        Self::try_aggregate_verify(pks, msgs, sig).is_ok()
+    }
+
+    /// Like `aggregate_verify`, but reports why verification failed
+    fn try_aggregate_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        _unique_help(msgs)?;
+        <Self as BLSSigCore<X>>::try_core_aggregate_verify(pks, msgs, sig, Self::CSUITE)
+    }
+
+    /// Like `try_aggregate_verify`, but skips `key_validate` on the public keys
+    fn try_aggregate_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        _unique_help(msgs)?;
+        <Self as BLSSigCore<X>>::try_core_aggregate_verify_prevalidated(
+            pks,
+            msgs,
+            sig,
+            Self::CSUITE,
+        )
+    }
+
+    /// Verify independent (pk, msg, sig) triples as a batch; see `core_batch_verify`.
+    /// Unlike `aggregate_verify`, messages need not be distinct.
+    fn batch_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], msgs: &[B], sigs: &[Self]) -> bool {
+        Self::try_batch_verify(pks, msgs, sigs).is_ok()
+    }
 
-        <Self as BLSSigCore<X>>::core_aggregate_verify(pks, msgs, sig, Self::CSUITE)
+    /// Like `batch_verify`, but reports why verification failed
+    fn try_batch_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_batch_verify(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Like `try_batch_verify`, but skips `key_validate` on the public keys
+    fn try_batch_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_batch_verify_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Find the invalid entries among independent (pk, msg, sig) triples;
+    /// see `core_batch_find_invalid`
+    fn batch_find_invalid<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<Vec<usize>, BlsError> {
+        <Self as BLSSigCore<X>>::core_batch_find_invalid(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Like `batch_find_invalid`, but skips `key_validate` on the public keys
+    fn batch_find_invalid_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<Vec<usize>, BlsError> {
+        <Self as BLSSigCore<X>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
     }
 }
 
@@ -134,41 +809,180 @@ pub trait BLSSignatureAug<X: ExpandMsg>: BLSSigCore<X> {
 
     /// turn a public key into a vector
     fn pk_bytes(pk: &Self::PKType, size_hint: usize) -> Vec<u8> {
+        // serializing into a Vec cannot fail
+        Self::try_pk_bytes(pk, size_hint).unwrap()
+    }
+
+    /// Like `pk_bytes`, but returns an error instead of panicking
+    fn try_pk_bytes(pk: &Self::PKType, size_hint: usize) -> Result<Vec<u8>, BlsError> {
         // 96 bytes of overhead for the PK, plus the size hint
         let mut cur = Cursor::new(Vec::<u8>::with_capacity(size_hint + Self::PK_LEN));
-        assert!(pk.serialize(&mut cur, true).is_ok());
-        cur.into_inner()
+        pk.serialize(&mut cur, true)?;
+        Ok(cur.into_inner())
     }
 
     /// augment message and then invoke coresign
     fn sign<B: AsRef<[u8]>>(x_prime: ScalarT<Self>, msg: B) -> Self {
+        // serializing into a Vec cannot fail
+        Self::try_sign(x_prime, msg).unwrap()
+    }
+
+    /// Like `sign`, but returns an error instead of panicking
+    fn try_sign<B: AsRef<[u8]>>(mut x_prime: ScalarT<Self>, msg: B) -> Result<Self, BlsError> {
         let pk = {
             let mut tmp = <Self::PKType as CurveProjective>::one();
             tmp.mul_assign(x_prime);
             tmp
         };
-        let mut pk_msg_vec = Self::pk_bytes(&pk, msg.as_ref().len());
-        pk_msg_vec.extend_from_slice(msg.as_ref());
-        <Self as BLSSigCore<X>>::core_sign(x_prime, &pk_msg_vec, Self::CSUITE)
+        let ret = Self::try_pk_bytes(&pk, msg.as_ref().len()).map(|mut pk_msg_vec| {
+            pk_msg_vec.extend_from_slice(msg.as_ref());
+            <Self as BLSSigCore<X>>::core_sign(x_prime, &pk_msg_vec, Self::CSUITE)
+        });
+        x_prime.zeroize();
+        ret
     }
 
     /// augment message and then invoke coreverify
     fn verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> bool {
-        let mut pk_msg_vec = Self::pk_bytes(&pk, msg.as_ref().len());
+        Self::try_verify(pk, sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    fn try_verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::key_validate(&pk)?;
+        Self::try_verify_prevalidated(pk, sig, msg)
+    }
+
+    /// Like `try_verify`, but skips `key_validate` on the public key
+    fn try_verify_prevalidated<B: AsRef<[u8]>>(
+        pk: Self::PKType,
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        let mut pk_msg_vec = Self::try_pk_bytes(&pk, msg.as_ref().len())?;
         pk_msg_vec.extend_from_slice(msg.as_ref());
-        <Self as BLSSigCore<X>>::core_verify(pk, sig, &pk_msg_vec, Self::CSUITE)
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(pk, sig, &pk_msg_vec, Self::CSUITE)
     }
 
     /// augment all messages and then invoke coreverify
     fn aggregate_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], msgs: &[B], sig: Self) -> bool {
-        let mut pks_msgs_vec = Vec::<Vec<u8>>::with_capacity(msgs.len());
-        for (msg, pk) in msgs.iter().zip(pks) {
-            let mut pk_msg_vec = Self::pk_bytes(&pk, msg.as_ref().len());
-            pk_msg_vec.extend_from_slice(msg.as_ref());
-            pks_msgs_vec.push(pk_msg_vec);
+        Self::try_aggregate_verify(pks, msgs, sig).is_ok()
+    }
+
+    /// Like `aggregate_verify`, but reports why verification failed
+    fn try_aggregate_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        for pk in pks {
+            <Self as BLSSigCore<X>>::key_validate(pk)?;
         }
-        <Self as BLSSigCore<X>>::core_aggregate_verify(pks, &pks_msgs_vec[..], sig, Self::CSUITE)
+        Self::try_aggregate_verify_prevalidated(pks, msgs, sig)
+    }
+
+    /// Like `try_aggregate_verify`, but skips `key_validate` on the public keys
+    fn try_aggregate_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        let pks_msgs_vec = _aug_help::<X, Self, B>(pks, msgs)?;
+        <Self as BLSSigCore<X>>::try_core_aggregate_verify_prevalidated(
+            pks,
+            &pks_msgs_vec[..],
+            sig,
+            Self::CSUITE,
+        )
+    }
+
+    /// Augment all messages and then verify the triples as a batch; see `core_batch_verify`
+    fn batch_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], msgs: &[B], sigs: &[Self]) -> bool {
+        Self::try_batch_verify(pks, msgs, sigs).is_ok()
+    }
+
+    /// Like `batch_verify`, but reports why verification failed
+    fn try_batch_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<(), BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        for pk in pks {
+            <Self as BLSSigCore<X>>::key_validate(pk)?;
+        }
+        Self::try_batch_verify_prevalidated(pks, msgs, sigs)
+    }
+
+    /// Like `try_batch_verify`, but skips `key_validate` on the public keys
+    fn try_batch_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<(), BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let pks_msgs_vec = _aug_help::<X, Self, B>(pks, msgs)?;
+        <Self as BLSSigCore<X>>::try_core_batch_verify_prevalidated(
+            &mut thread_rng(),
+            pks,
+            &pks_msgs_vec[..],
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Augment all messages and then find the invalid entries among the triples;
+    /// see `core_batch_find_invalid`
+    fn batch_find_invalid<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<Vec<usize>, BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let pks_msgs_vec = _aug_help::<X, Self, B>(pks, msgs)?;
+        <Self as BLSSigCore<X>>::core_batch_find_invalid(
+            &mut thread_rng(),
+            pks,
+            &pks_msgs_vec[..],
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Like `batch_find_invalid`, but skips `key_validate` on the public keys
+    fn batch_find_invalid_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<Vec<usize>, BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let pks_msgs_vec = _aug_help::<X, Self, B>(pks, msgs)?;
+        <Self as BLSSigCore<X>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            pks,
+            &pks_msgs_vec[..],
+            sigs,
+            Self::CSUITE,
+        )
+    }
+}
+
+// PK || msg for each (PK, msg) pair: used in Aug aggregate and batch verify
+fn _aug_help<X, T, B>(pks: &[T::PKType], msgs: &[B]) -> Result<Vec<Vec<u8>>, BlsError>
+where
+    X: ExpandMsg,
+    T: BLSSignatureAug<X>,
+    B: AsRef<[u8]>,
+{
+    let mut pks_msgs_vec = Vec::<Vec<u8>>::with_capacity(msgs.len());
+    for (msg, pk) in msgs.iter().zip(pks) {
+        let mut pk_msg_vec = T::try_pk_bytes(pk, msg.as_ref().len())?;
+        pk_msg_vec.extend_from_slice(msg.as_ref());
+        pks_msgs_vec.push(pk_msg_vec);
     }
+    Ok(pks_msgs_vec)
 }
 
 /// BLS signature with proof of possession
@@ -189,126 +1003,485 @@ pub trait BLSSignaturePop<X: ExpandMsg>: BLSSigCore<X> {
 
     /// re-export from BLSSigCore
     fn verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> bool {
-        <Self as BLSSigCore<X>>::core_verify(pk, sig, msg, Self::CSUITE)
+        Self::try_verify(pk, sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    fn try_verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify(pk, sig, msg, Self::CSUITE)
+    }
+
+    /// Like `try_verify`, but skips `key_validate` on the public key
+    fn try_verify_prevalidated<B: AsRef<[u8]>>(
+        pk: Self::PKType,
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(pk, sig, msg, Self::CSUITE)
     }
 
     /// just invoke verify from BLSSigCore
     fn aggregate_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], msgs: &[B], sig: Self) -> bool {
-        <Self as BLSSigCore<X>>::core_aggregate_verify(pks, msgs, sig, Self::CSUITE)
+        Self::try_aggregate_verify(pks, msgs, sig).is_ok()
+    }
+
+    /// Like `aggregate_verify`, but reports why verification failed
+    fn try_aggregate_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_aggregate_verify(pks, msgs, sig, Self::CSUITE)
+    }
+
+    /// Like `try_aggregate_verify`, but skips `key_validate` on the public keys
+    fn try_aggregate_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sig: Self,
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_aggregate_verify_prevalidated(
+            pks,
+            msgs,
+            sig,
+            Self::CSUITE,
+        )
+    }
+
+    /// Verify independent (pk, msg, sig) triples as a batch; see `core_batch_verify`
+    fn batch_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], msgs: &[B], sigs: &[Self]) -> bool {
+        Self::try_batch_verify(pks, msgs, sigs).is_ok()
+    }
+
+    /// Like `batch_verify`, but reports why verification failed
+    fn try_batch_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_batch_verify(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Like `try_batch_verify`, but skips `key_validate` on the public keys
+    fn try_batch_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_batch_verify_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Find the invalid entries among independent (pk, msg, sig) triples;
+    /// see `core_batch_find_invalid`
+    fn batch_find_invalid<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<Vec<usize>, BlsError> {
+        <Self as BLSSigCore<X>>::core_batch_find_invalid(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
+    }
+
+    /// Like `batch_find_invalid`, but skips `key_validate` on the public keys
+    fn batch_find_invalid_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        msgs: &[B],
+        sigs: &[Self],
+    ) -> Result<Vec<usize>, BlsError> {
+        <Self as BLSSigCore<X>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            pks,
+            msgs,
+            sigs,
+            Self::CSUITE,
+        )
     }
 
     /// verify a multisig
     fn multisig_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], sig: Self, msg: B) -> bool {
+        Self::try_multisig_verify(pks, sig, msg).is_ok()
+    }
+
+    /// Like `multisig_verify`, but reports why verification failed
+    fn try_multisig_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        for pk in pks {
+            <Self as BLSSigCore<X>>::key_validate(pk)?;
+        }
+        Self::try_multisig_verify_prevalidated(pks, sig, msg)
+    }
+
+    /// Like `try_multisig_verify`, but skips `key_validate` on the individual public keys.
+    /// The aggregate public key is still rejected if it is the identity.
+    fn try_multisig_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        if pks.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
         let apk = _agg_help(pks);
-        <Self as BLSSigCore<X>>::core_verify(apk, sig, msg, Self::CSUITE)
+        if apk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(apk, sig, msg, Self::CSUITE)
+    }
+
+    /// Verify a weighted multisignature: `sig` must be sum_i w_i * sig_i, where sig_i is
+    /// a signature on `msg` under pk_i and (pk_i, w_i) ranges over `signers`. Fails if the
+    /// total weight of `signers` is below `min_weight`.
+    /// The caller must have checked a proof of possession for every key.
+    fn weighted_multisig_verify<B: AsRef<[u8]>>(
+        signers: &[(Self::PKType, u64)],
+        sig: Self,
+        msg: B,
+        min_weight: u64,
+    ) -> bool {
+        Self::try_weighted_multisig_verify(signers, sig, msg, min_weight).is_ok()
+    }
+
+    /// Like `weighted_multisig_verify`, but reports why verification failed
+    fn try_weighted_multisig_verify<B: AsRef<[u8]>>(
+        signers: &[(Self::PKType, u64)],
+        sig: Self,
+        msg: B,
+        min_weight: u64,
+    ) -> Result<(), BlsError> {
+        for (pk, _) in signers {
+            <Self as BLSSigCore<X>>::key_validate(pk)?;
+        }
+        Self::try_weighted_multisig_verify_prevalidated(signers, sig, msg, min_weight)
+    }
+
+    /// Like `try_weighted_multisig_verify`, but skips `key_validate` on the individual
+    /// public keys. The aggregate public key is still rejected if it is the identity.
+    fn try_weighted_multisig_verify_prevalidated<B: AsRef<[u8]>>(
+        signers: &[(Self::PKType, u64)],
+        sig: Self,
+        msg: B,
+        min_weight: u64,
+    ) -> Result<(), BlsError> {
+        let apk = _weighted_apk(signers, min_weight)?;
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(apk, sig, msg, Self::CSUITE)
     }
 
     /// prove possession
     fn pop_prove<B: AsRef<[u8]>>(sk: B) -> Self {
-        let (x_prime, pk) = <Self as BLSSigCore<X>>::keygen(sk);
-        let pk_bytes = {
-            let mut buf = GenericArray::<u8, Self::Length>::default();
-            let mut cur = Cursor::new(&mut buf[..]);
-            assert!(pk.serialize(&mut cur, true).is_ok());
-            buf
-        };
-        <Self as BLSSigCore<X>>::core_sign(x_prime, &pk_bytes[..], Self::CSUITE_POP)
+        // HKDF and serializing into a fixed-size buffer cannot fail
+        Self::try_pop_prove(sk).unwrap()
+    }
+
+    /// Like `pop_prove`, but returns an error instead of panicking
+    fn try_pop_prove<B: AsRef<[u8]>>(sk: B) -> Result<Self, BlsError> {
+        let (mut x_prime, pk) = <Self as BLSSigCore<X>>::try_keygen(sk)?;
+        let mut pk_bytes = GenericArray::<u8, Self::Length>::default();
+        let ret = _pk_ser_help(&pk, &mut pk_bytes[..])
+            .map(|_| <Self as BLSSigCore<X>>::core_sign(x_prime, &pk_bytes[..], Self::CSUITE_POP));
+        x_prime.zeroize();
+        ret
     }
 
     /// check proof of possession
     fn pop_verify(pk: <Self as BLSSigCore<X>>::PKType, sig: Self) -> bool {
-        let pk_bytes = {
-            let mut buf = GenericArray::<u8, Self::Length>::default();
-            let mut cur = Cursor::new(&mut buf[..]);
-            assert!(pk.serialize(&mut cur, true).is_ok());
-            buf
-        };
-        <Self as BLSSigCore<X>>::core_verify(pk, sig, &pk_bytes[..], Self::CSUITE_POP)
+        Self::try_pop_verify(pk, sig).is_ok()
+    }
+
+    /// Like `pop_verify`, but reports why verification failed
+    fn try_pop_verify(pk: <Self as BLSSigCore<X>>::PKType, sig: Self) -> Result<(), BlsError> {
+        let mut pk_bytes = GenericArray::<u8, Self::Length>::default();
+        _pk_ser_help(&pk, &mut pk_bytes[..])?;
+        <Self as BLSSigCore<X>>::try_core_verify(pk, sig, &pk_bytes[..], Self::CSUITE_POP)
+    }
+}
+
+/// BLS multisignatures without proofs of possession, in the style of Boneh, Drijvers, and
+/// Neven (https://eprint.iacr.org/2018/483).
+///
+/// Each public key pk_i in a multisignature gets a coefficient t_i, a hash of pk_i and of
+/// the whole list of keys. The aggregate public key is sum_i t_i * pk_i, and the
+/// multisignature is sum_i t_i * sig_i, so an attacker cannot choose its key to cancel
+/// out the others'. The coefficients depend on the order of the keys: signers and
+/// verifiers must agree on it.
+pub trait BLSSignatureBdn<X: ExpandMsg>: BLSSigCore<X> {
+    /// Ciphersuite tag
+    const CSUITE: &'static [u8];
+
+    /// Ciphersuite tag for hashing public keys to coefficients
+    const CSUITE_COEF: &'static [u8];
+
+    /// re-export from BLSSigCore
+    fn sign<B: AsRef<[u8]>>(x_prime: ScalarT<Self>, msg: B) -> Self {
+        <Self as BLSSigCore<X>>::core_sign(x_prime, msg, Self::CSUITE)
+    }
+
+    /// Verify one signer's signature, before it is aggregated
+    fn verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> bool {
+        Self::try_verify(pk, sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    fn try_verify<B: AsRef<[u8]>>(pk: Self::PKType, sig: Self, msg: B) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify(pk, sig, msg, Self::CSUITE)
+    }
+
+    /// Like `try_verify`, but skips `key_validate` on the public key
+    fn try_verify_prevalidated<B: AsRef<[u8]>>(
+        pk: Self::PKType,
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(pk, sig, msg, Self::CSUITE)
+    }
+
+    /// The aggregate public key sum_i t_i * pks[i]
+    fn bdn_aggregate_pks(pks: &[Self::PKType]) -> Result<Self::PKType, BlsError> {
+        if pks.is_empty() {
+            return Err(BlsError::EmptyInput);
+        }
+        Ok(_bdn_agg_help::<X, _, _>(pks, pks, Self::CSUITE_COEF))
+    }
+
+    /// The multisignature sum_i t_i * sigs[i], where sigs[i] is the signature under pks[i]
+    fn bdn_aggregate(pks: &[Self::PKType], sigs: &[Self]) -> Result<Self, BlsError> {
+        _len_check(pks.len(), sigs.len())?;
+        Ok(_bdn_agg_help::<X, _, _>(pks, sigs, Self::CSUITE_COEF))
+    }
+
+    /// Verify a multisignature on `msg` by every key in `pks`, in order
+    fn multisig_verify<B: AsRef<[u8]>>(pks: &[Self::PKType], sig: Self, msg: B) -> bool {
+        Self::try_multisig_verify(pks, sig, msg).is_ok()
+    }
+
+    /// Like `multisig_verify`, but reports why verification failed
+    fn try_multisig_verify<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        for pk in pks {
+            <Self as BLSSigCore<X>>::key_validate(pk)?;
+        }
+        Self::try_multisig_verify_prevalidated(pks, sig, msg)
+    }
+
+    /// Like `try_multisig_verify`, but skips `key_validate` on the individual public keys.
+    /// The aggregate public key is still rejected if it is the identity.
+    fn try_multisig_verify_prevalidated<B: AsRef<[u8]>>(
+        pks: &[Self::PKType],
+        sig: Self,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        let apk = Self::bdn_aggregate_pks(pks)?;
+        if apk.is_zero() {
+            return Err(BlsError::IdentityPoint);
+        }
+        <Self as BLSSigCore<X>>::try_core_verify_prevalidated(apk, sig, msg, Self::CSUITE)
     }
 }
 
 impl<X: ExpandMsg> BLSSigCore<X> for G1 {
     type PKType = G2;
 
-    fn keygen<B: AsRef<[u8]>>(sk: B) -> (Fr, G2) {
-        let x_prime = xprime_from_sk(sk);
+    fn key_gen_with_info<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        ikm: B,
+        key_info: C,
+        mode: KeyGenMode,
+    ) -> Result<(Fr, G2), BlsError> {
+        let x_prime = key_gen(ikm, key_info, mode)?;
         let mut pk = G2::one();
         pk.mul_assign(x_prime);
-        (x_prime, pk)
+        Ok((x_prime, pk))
     }
 
-    fn core_sign<B: AsRef<[u8]>, C: AsRef<[u8]>>(x_prime: Fr, msg: B, ciphersuite: C) -> G1 {
+    fn core_sign<B: AsRef<[u8]>, C: AsRef<[u8]>>(mut x_prime: Fr, msg: B, ciphersuite: C) -> G1 {
         let mut p = <G1 as HashToCurve<X>>::hash_to_curve(msg, ciphersuite);
         p.mul_assign(x_prime);
+        x_prime.zeroize();
         p
     }
 
-    fn core_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+    fn try_core_verify_prevalidated<B: AsRef<[u8]>, C: AsRef<[u8]>>(
         pk: G2,
         sig: G1,
         msg: B,
         ciphersuite: C,
-    ) -> bool {
+    ) -> Result<(), BlsError> {
         let p = <G1 as HashToCurve<X>>::hash_to_curve(msg, ciphersuite)
             .into_affine()
             .prepare();
-        let g2gen = {
-            let mut tmp = G2::one();
-            tmp.negate();
-            tmp.into_affine().prepare()
-        };
+        <G1 as BLSSigCore<X>>::try_core_verify_hashed_prevalidated(pk, sig, &p)
+    }
 
-        match Bls12::final_exponentiation(&Bls12::miller_loop(&[
-            (&p, &pk.into_affine().prepare()),
-            (&sig.into_affine().prepare(), &g2gen),
-        ])) {
-            None => false,
-            Some(pairingproduct) => pairingproduct == Fq12::one(),
-        }
+    fn hash_to_point<B: AsRef<[u8]>, C: AsRef<[u8]>>(msg: B, ciphersuite: C) -> G1 {
+        <G1 as HashToCurve<X>>::hash_to_curve(msg, ciphersuite)
     }
 
-    fn core_aggregate_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+    fn try_core_verify_prepared(pk: &G2Prep, sig: G1, hashed: &G1Prep) -> Result<(), BlsError> {
+        <G1 as BLSSigCore<X>>::sig_subgroup_check(&sig)?;
+        _pairing_check(Bls12::final_exponentiation(&Bls12::miller_loop(&[
+            (hashed, pk),
+            (&sig.into_affine().prepare(), _neg_g2_prep()),
+        ])))
+    }
+
+    fn try_core_aggregate_verify_prevalidated<B: AsRef<[u8]>, C: AsRef<[u8]>>(
         pks: &[G2],
         msgs: &[B],
         sig: G1,
         ciphersuite: C,
-    ) -> bool {
-        let pvec = {
-            let mut ret =
-                Vec::<<<G1 as CurveProjective>::Affine as CurveAffine>::Prepared>::with_capacity(
-                    msgs.len() + 1,
-                );
-            for msg in msgs {
-                ret.push(
-                    <G1 as HashToCurve<X>>::hash_to_curve(msg, &ciphersuite)
-                        .into_affine()
-                        .prepare(),
-                );
-            }
-            ret.push(sig.into_affine().prepare());
-            ret
-        };
-        let qvec = {
-            let mut ret =
-                Vec::<<<G2 as CurveProjective>::Affine as CurveAffine>::Prepared>::with_capacity(
-                    pks.len() + 1,
-                );
-            for pk in pks {
-                ret.push(pk.into_affine().prepare());
-            }
-            let mut tmp = G2::one();
-            tmp.negate();
-            ret.push(tmp.into_affine().prepare());
-            ret
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        let pks = _map_help(pks.len(), |i| pks[i].into_affine().prepare());
+        let pks: Vec<&G2Prep> = pks.iter().collect();
+        <G1 as BLSSigCore<X>>::try_core_aggregate_verify_prepared(&pks[..], msgs, sig, ciphersuite)
+    }
+
+    fn try_core_aggregate_verify_prepared<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pks: &[&G2Prep],
+        msgs: &[B],
+        sig: G1,
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        <G1 as BLSSigCore<X>>::sig_subgroup_check(&sig)?;
+        let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_ref()).collect();
+        let ciphersuite = ciphersuite.as_ref();
+        // e(H(msg_i), pk_i) for each i, times e(sig, -g2)
+        let hashed = _map_help(pks.len(), |i| {
+            <G1 as HashToCurve<X>>::hash_to_curve(msgs[i], ciphersuite)
+                .into_affine()
+                .prepare()
+        });
+        let pairs: Vec<_> = hashed.iter().zip(pks).map(|(h, pk)| (h, *pk)).collect();
+        _batch_check(&pairs[..], (&_prep_sum(&[sig]), _neg_g2_prep()))
+    }
+
+    fn try_core_aggregate_verify_hashed_prevalidated(
+        pks: &[G2],
+        hashed: &[&G1Prep],
+        sig: G1,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), hashed.len())?;
+        <G1 as BLSSigCore<X>>::sig_subgroup_check(&sig)?;
+        // e(H(msg_i), pk_i) for each i, times e(sig, -g2)
+        let pks = _map_help(pks.len(), |i| pks[i].into_affine().prepare());
+        let pairs: Vec<_> = hashed.iter().zip(&pks).map(|(h, pk)| (*h, pk)).collect();
+        _batch_check(&pairs[..], (&_prep_sum(&[sig]), _neg_g2_prep()))
+    }
+
+    fn try_core_batch_verify_prevalidated<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[G2],
+        msgs: &[B],
+        sigs: &[G1],
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        for sig in sigs {
+            <G1 as BLSSigCore<X>>::sig_subgroup_check(sig)?;
+        }
+        let (pairs, rsigs) = _batch_terms_g1::<X, R, B, C>(rng, pks, msgs, sigs, ciphersuite);
+        _batch_check(
+            &_pair_refs(&pairs[..])[..],
+            (&_prep_sum(&rsigs[..]), _neg_g2_prep()),
+        )
+    }
+
+    fn core_batch_find_invalid_prevalidated<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[G2],
+        msgs: &[B],
+        sigs: &[G1],
+        ciphersuite: C,
+    ) -> Result<Vec<usize>, BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let (mut bad, good) = _split_subgroup::<X, G1>(sigs);
+        let good_pks: Vec<_> = good.iter().map(|&i| pks[i]).collect();
+        let good_msgs: Vec<_> = good.iter().map(|&i| msgs[i].as_ref()).collect();
+        let good_sigs: Vec<_> = good.iter().map(|&i| sigs[i]).collect();
+        let (pairs, rsigs) =
+            _batch_terms_g1::<X, R, _, C>(rng, &good_pks, &good_msgs, &good_sigs, ciphersuite);
+        let pairs = _pair_refs(&pairs[..]);
+        let check = |lo: usize, hi: usize| {
+            let sum = _prep_sum(&rsigs[lo..hi]);
+            _batch_check(&pairs[lo..hi], (&sum, _neg_g2_prep())).is_ok()
         };
+        bad.extend(
+            _find_invalid_help(pairs.len(), &check)
+                .into_iter()
+                .map(|j| good[j]),
+        );
+        bad.sort_unstable();
+        Ok(bad)
+    }
+}
 
-        // XXX: this is annoying: miller_loop requires an iter to tuple refs, not tuples
-        let pqz: Vec<_> = pvec.as_slice().iter().zip(qvec.as_slice()).collect();
-        match Bls12::final_exponentiation(&Bls12::miller_loop(&pqz[..])) {
-            None => false,
-            Some(pairingproduct) => pairingproduct == Fq12::one(),
+// per-entry batch terms for G1 signatures: the prepared pairs (r_i * H(msg_i), pk_i)
+// and the scaled signatures r_i * sig_i
+fn _batch_terms_g1<X, R, B, C>(
+    rng: &mut R,
+    pks: &[G2],
+    msgs: &[B],
+    sigs: &[G1],
+    ciphersuite: C,
+) -> (Vec<(G1Prep, G2Prep)>, Vec<G1>)
+where
+    X: ExpandMsg,
+    R: Rng,
+    B: AsRef<[u8]>,
+    C: AsRef<[u8]>,
+{
+    // draw the scalars serially so the result does not depend on the thread count
+    let rs: Vec<_> = (0..sigs.len()).map(|_| _batch_scalar(rng)).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_ref()).collect();
+    let ciphersuite = ciphersuite.as_ref();
+    _map_help(sigs.len(), |i| {
+        let mut p = <G1 as HashToCurve<X>>::hash_to_curve(msgs[i], ciphersuite);
+        p.mul_assign(rs[i]);
+        let mut s = sigs[i];
+        s.mul_assign(rs[i]);
+        (
+            (p.into_affine().prepare(), pks[i].into_affine().prepare()),
+            s,
+        )
+    })
+    .into_iter()
+    .unzip()
+}
+
+// split batch entries into those whose signatures fail the subgroup check and the rest
+fn _split_subgroup<X: ExpandMsg, G: BLSSigCore<X>>(sigs: &[G]) -> (Vec<usize>, Vec<usize>) {
+    let mut bad = Vec::new();
+    let mut good = Vec::with_capacity(sigs.len());
+    for (i, sig) in sigs.iter().enumerate() {
+        if G::sig_subgroup_check(sig).is_ok() {
+            good.push(i);
+        } else {
+            bad.push(i);
         }
     }
+    (bad, good)
 }
 
 impl BLSSignatureBasic<ExpandMsgXmd<Sha256>> for G1 {
@@ -326,88 +1499,178 @@ impl BLSSignaturePop<ExpandMsgXmd<Sha256>> for G1 {
     type Length = U96;
 }
 
+impl BLSSignatureBdn<ExpandMsgXmd<Sha256>> for G1 {
+    const CSUITE: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_BDN_";
+    const CSUITE_COEF: &'static [u8] = b"BLS_COEF_BLS12381G1_XMD:SHA-256_SSWU_RO_BDN_";
+}
+
 impl<X: ExpandMsg> BLSSigCore<X> for G2 {
     type PKType = G1;
 
-    fn keygen<B: AsRef<[u8]>>(sk: B) -> (Fr, G1) {
-        let x_prime = xprime_from_sk(sk);
+    fn key_gen_with_info<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        ikm: B,
+        key_info: C,
+        mode: KeyGenMode,
+    ) -> Result<(Fr, G1), BlsError> {
+        let x_prime = key_gen(ikm, key_info, mode)?;
         let mut pk = G1::one();
         pk.mul_assign(x_prime);
-        (x_prime, pk)
+        Ok((x_prime, pk))
     }
 
-    fn core_sign<B: AsRef<[u8]>, C: AsRef<[u8]>>(x_prime: Fr, msg: B, ciphersuite: C) -> G2 {
+    fn core_sign<B: AsRef<[u8]>, C: AsRef<[u8]>>(mut x_prime: Fr, msg: B, ciphersuite: C) -> G2 {
         let mut p = <G2 as HashToCurve<X>>::hash_to_curve(msg, ciphersuite);
         p.mul_assign(x_prime);
+        x_prime.zeroize();
         p
     }
 
-    fn core_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+    fn try_core_verify_prevalidated<B: AsRef<[u8]>, C: AsRef<[u8]>>(
         pk: G1,
         sig: G2,
         msg: B,
         ciphersuite: C,
-    ) -> bool {
+    ) -> Result<(), BlsError> {
         let p = <G2 as HashToCurve<X>>::hash_to_curve(msg, ciphersuite)
             .into_affine()
             .prepare();
-        let g1gen = {
-            let mut tmp = G1::one();
-            tmp.negate();
-            tmp.into_affine().prepare()
-        };
+        <G2 as BLSSigCore<X>>::try_core_verify_hashed_prevalidated(pk, sig, &p)
+    }
 
-        match Bls12::final_exponentiation(&Bls12::miller_loop(&[
-            (&pk.into_affine().prepare(), &p),
-            (&g1gen, &sig.into_affine().prepare()),
-        ])) {
-            None => false,
-            Some(pairingproduct) => pairingproduct == Fq12::one(),
-        }
+    fn hash_to_point<B: AsRef<[u8]>, C: AsRef<[u8]>>(msg: B, ciphersuite: C) -> G2 {
+        <G2 as HashToCurve<X>>::hash_to_curve(msg, ciphersuite)
     }
 
-    fn core_aggregate_verify<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+    fn try_core_verify_prepared(pk: &G1Prep, sig: G2, hashed: &G2Prep) -> Result<(), BlsError> {
+        <G2 as BLSSigCore<X>>::sig_subgroup_check(&sig)?;
+        _pairing_check(Bls12::final_exponentiation(&Bls12::miller_loop(&[
+            (pk, hashed),
+            (_neg_g1_prep(), &sig.into_affine().prepare()),
+        ])))
+    }
+
+    fn try_core_aggregate_verify_prevalidated<B: AsRef<[u8]>, C: AsRef<[u8]>>(
         pks: &[G1],
         msgs: &[B],
         sig: G2,
         ciphersuite: C,
-    ) -> bool {
-        let pvec = {
-            let mut ret =
-                Vec::<<<G1 as CurveProjective>::Affine as CurveAffine>::Prepared>::with_capacity(
-                    pks.len() + 1,
-                );
-            for pk in pks {
-                ret.push(pk.into_affine().prepare());
-            }
-            let mut tmp = G1::one();
-            tmp.negate();
-            ret.push(tmp.into_affine().prepare());
-            ret
-        };
-        let qvec = {
-            let mut ret =
-                Vec::<<<G2 as CurveProjective>::Affine as CurveAffine>::Prepared>::with_capacity(
-                    msgs.len() + 1,
-                );
-            for msg in msgs {
-                ret.push(
-                    <G2 as HashToCurve<X>>::hash_to_curve(msg, &ciphersuite)
-                        .into_affine()
-                        .prepare(),
-                );
-            }
-            ret.push(sig.into_affine().prepare());
-            ret
-        };
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        let pks = _map_help(pks.len(), |i| pks[i].into_affine().prepare());
+        let pks: Vec<&G1Prep> = pks.iter().collect();
+        <G2 as BLSSigCore<X>>::try_core_aggregate_verify_prepared(&pks[..], msgs, sig, ciphersuite)
+    }
+
+    fn try_core_aggregate_verify_prepared<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        pks: &[&G1Prep],
+        msgs: &[B],
+        sig: G2,
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), msgs.len())?;
+        <G2 as BLSSigCore<X>>::sig_subgroup_check(&sig)?;
+        let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_ref()).collect();
+        let ciphersuite = ciphersuite.as_ref();
+        // e(pk_i, H(msg_i)) for each i, times e(-g1, sig)
+        let hashed = _map_help(pks.len(), |i| {
+            <G2 as HashToCurve<X>>::hash_to_curve(msgs[i], ciphersuite)
+                .into_affine()
+                .prepare()
+        });
+        let pairs: Vec<_> = pks.iter().zip(&hashed).map(|(pk, h)| (*pk, h)).collect();
+        _batch_check(&pairs[..], (_neg_g1_prep(), &_prep_sum(&[sig])))
+    }
+
+    fn try_core_aggregate_verify_hashed_prevalidated(
+        pks: &[G1],
+        hashed: &[&G2Prep],
+        sig: G2,
+    ) -> Result<(), BlsError> {
+        _len_check(pks.len(), hashed.len())?;
+        <G2 as BLSSigCore<X>>::sig_subgroup_check(&sig)?;
+        // e(pk_i, H(msg_i)) for each i, times e(-g1, sig)
+        let pks = _map_help(pks.len(), |i| pks[i].into_affine().prepare());
+        let pairs: Vec<_> = pks.iter().zip(hashed).map(|(pk, h)| (pk, *h)).collect();
+        _batch_check(&pairs[..], (_neg_g1_prep(), &_prep_sum(&[sig])))
+    }
 
-        // XXX: this is annoying: miller_loop requires an iter to tuple refs, not tuples
-        let pqz: Vec<_> = pvec.as_slice().iter().zip(qvec.as_slice()).collect();
-        match Bls12::final_exponentiation(&Bls12::miller_loop(&pqz[..])) {
-            None => false,
-            Some(pairingproduct) => pairingproduct == Fq12::one(),
+    fn try_core_batch_verify_prevalidated<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[G1],
+        msgs: &[B],
+        sigs: &[G2],
+        ciphersuite: C,
+    ) -> Result<(), BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        for sig in sigs {
+            <G2 as BLSSigCore<X>>::sig_subgroup_check(sig)?;
         }
+        let (pairs, rsigs) = _batch_terms_g2::<X, R, B, C>(rng, pks, msgs, sigs, ciphersuite);
+        _batch_check(
+            &_pair_refs(&pairs[..])[..],
+            (_neg_g1_prep(), &_prep_sum(&rsigs[..])),
+        )
     }
+
+    fn core_batch_find_invalid_prevalidated<R: Rng, B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        rng: &mut R,
+        pks: &[G1],
+        msgs: &[B],
+        sigs: &[G2],
+        ciphersuite: C,
+    ) -> Result<Vec<usize>, BlsError> {
+        _batch_len_check(pks.len(), msgs.len(), sigs.len())?;
+        let (mut bad, good) = _split_subgroup::<X, G2>(sigs);
+        let good_pks: Vec<_> = good.iter().map(|&i| pks[i]).collect();
+        let good_msgs: Vec<_> = good.iter().map(|&i| msgs[i].as_ref()).collect();
+        let good_sigs: Vec<_> = good.iter().map(|&i| sigs[i]).collect();
+        let (pairs, rsigs) =
+            _batch_terms_g2::<X, R, _, C>(rng, &good_pks, &good_msgs, &good_sigs, ciphersuite);
+        let pairs = _pair_refs(&pairs[..]);
+        let check = |lo: usize, hi: usize| {
+            let sum = _prep_sum(&rsigs[lo..hi]);
+            _batch_check(&pairs[lo..hi], (_neg_g1_prep(), &sum)).is_ok()
+        };
+        bad.extend(
+            _find_invalid_help(pairs.len(), &check)
+                .into_iter()
+                .map(|j| good[j]),
+        );
+        bad.sort_unstable();
+        Ok(bad)
+    }
+}
+
+// per-entry batch terms for G2 signatures: the prepared pairs (r_i * pk_i, H(msg_i))
+// and the scaled signatures r_i * sig_i. The random scalars go on the G1 side because
+// multiplication there is cheaper.
+fn _batch_terms_g2<X, R, B, C>(
+    rng: &mut R,
+    pks: &[G1],
+    msgs: &[B],
+    sigs: &[G2],
+    ciphersuite: C,
+) -> (Vec<(G1Prep, G2Prep)>, Vec<G2>)
+where
+    X: ExpandMsg,
+    R: Rng,
+    B: AsRef<[u8]>,
+    C: AsRef<[u8]>,
+{
+    // draw the scalars serially so the result does not depend on the thread count
+    let rs: Vec<_> = (0..sigs.len()).map(|_| _batch_scalar(rng)).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_ref()).collect();
+    let ciphersuite = ciphersuite.as_ref();
+    _map_help(sigs.len(), |i| {
+        let mut p = pks[i];
+        p.mul_assign(rs[i]);
+        let q = <G2 as HashToCurve<X>>::hash_to_curve(msgs[i], ciphersuite);
+        let mut s = sigs[i];
+        s.mul_assign(rs[i]);
+        ((p.into_affine().prepare(), q.into_affine().prepare()), s)
+    })
+    .into_iter()
+    .unzip()
 }
 
 impl BLSSignatureBasic<ExpandMsgXmd<Sha256>> for G2 {
@@ -424,3 +1687,8 @@ impl BLSSignaturePop<ExpandMsgXmd<Sha256>> for G2 {
     const CSUITE_POP: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
     type Length = U48;
 }
+
+impl BLSSignatureBdn<ExpandMsgXmd<Sha256>> for G2 {
+    const CSUITE: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_BDN_";
+    const CSUITE_COEF: &'static [u8] = b"BLS_COEF_BLS12381G2_XMD:SHA-256_SSWU_RO_BDN_";
+}
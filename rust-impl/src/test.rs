@@ -1,9 +1,36 @@
-use super::signature::{xprime_from_sk, BLSSigCore};
-use ff::PrimeField;
-use pairing_plus::bls12_381::{Fr, FrRepr, G1, G2};
-use pairing_plus::hash_to_field::ExpandMsgXmd;
-use pairing_plus::CurveProjective;
-use sha2::Sha256;
+use super::aggregator::Aggregator;
+use super::bitfield::BitfieldAggregate;
+use super::ciphersuite::{
+    Ciphersuite, G1XmdSha256Aug, G1XmdSha256Basic, G1XmdSha256Pop, G1XofShake256Aug,
+    G1XofShake256Basic, G1XofShake256Pop, G2XmdSha256Aug, G2XmdSha256Basic, G2XmdSha256Pop,
+    G2XofShake256Aug, G2XofShake256Basic, G2XofShake256Pop, PkT, PopCiphersuite,
+};
+use super::committee::Committee;
+use super::coordinator::{SigningStatus, ThresholdCoordinator};
+use super::dkg::{DkgCommitment, DkgComplaint, DkgOutput, DkgParticipant, DkgShare};
+use super::encoding::{sk_from_bytes, sk_to_bytes, PointEncoding};
+use super::error::BlsError;
+use super::hashed::{HashCache, HashedMessage, Unaugmented};
+use super::prepared::PreparedPublicKey;
+use super::reshare::{ReshareDealer, ReshareRecipient, ShareRefresh};
+use super::signature::{
+    _agg_help, _bdn_coefficients, _multi_miller_loop, key_gen, weighted_aggregate, xprime_from_sk,
+    BLSSigCore, BLSSignatureAug, BLSSignatureBasic, BLSSignatureBdn, BLSSignaturePop, KeyGenMode,
+};
+use super::threshold::{
+    _lagrange_at_zero, split_secret, BLSSignatureThreshold, PartialSignature, PublicKeyShare,
+    SecretShare,
+};
+use super::typed::{Aug, Basic, Pop, PublicKey, Scheme, SecretKey, Signature};
+use super::vrf::BLSVrf;
+use ff::{Field, PrimeField, PrimeFieldRepr, SqrtField};
+use pairing_plus::bls12_381::{transmute, Bls12, Fq, FqRepr, Fr, FrRepr, G1, G2};
+use pairing_plus::hash_to_field::{ExpandMsg, ExpandMsgXmd};
+use pairing_plus::serdes::SerDes;
+use pairing_plus::{CurveAffine, CurveProjective, Engine, SubgroupCheck};
+use rand::thread_rng;
+use sha2::{Sha256, Sha512};
+use zeroize::Zeroize;
 
 fn test_sig<T: CurveProjective + BLSSigCore<ExpandMsgXmd<Sha256>>>(ciphersuite: &[u8]) {
     let msg = "this is the message";
@@ -34,3 +61,2761 @@ fn test_xprime_from_sk() {
     ]);
     assert_eq!(fr_val, Fr::from_repr(expect).unwrap());
 }
+
+fn test_typed<G: PointEncoding<Scalar = Fr> + SerDes, S: Scheme<G>>() {
+    let msg = "this is the message";
+    let sk = SecretKey::<G, S>::new("this is the key");
+    let pk = sk.public_key();
+    let sig = sk.sign(msg);
+    assert!(pk.verify(&sig, msg));
+    assert!(!pk.verify(&sig, "this is not the message"));
+
+    let pk2 = PublicKey::<G, S>::from_bytes(&pk.to_bytes()[..]).unwrap();
+    assert_eq!(pk, pk2);
+    let sig2 = Signature::<G, S>::from_bytes(&sig.to_bytes()[..]).unwrap();
+    assert_eq!(sig, sig2);
+    assert!(Signature::<G, S>::from_bytes(&pk.to_bytes()[..]).is_err());
+
+    let sk_bytes = sk.to_bytes();
+    assert_eq!(sk, SecretKey::<G, S>::from_bytes(&sk_bytes).unwrap());
+
+    let pk_c = pk.to_compressed();
+    assert_eq!(pk_c.as_ref(), &pk.to_bytes()[..]);
+    assert_eq!(pk, PublicKey::<G, S>::from_compressed(&pk_c).unwrap());
+    assert_eq!(
+        pk,
+        PublicKey::<G, S>::from_uncompressed(&pk.to_uncompressed()).unwrap()
+    );
+    let sig_c = sig.to_compressed();
+    assert_eq!(sig_c.as_ref(), &sig.to_bytes()[..]);
+    assert_eq!(sig, Signature::<G, S>::from_compressed(&sig_c).unwrap());
+    assert_eq!(
+        sig,
+        Signature::<G, S>::from_uncompressed(&sig.to_uncompressed()).unwrap()
+    );
+    assert_eq!(format!("{:?}", sk), "SecretKey(<redacted>)");
+
+    let mut sk_copy = sk.clone();
+    assert!(!sk_copy.as_scalar().is_zero());
+    sk_copy.zeroize();
+    assert!(sk_copy.as_scalar().is_zero());
+
+    let sk3 = SecretKey::<G, S>::new("this is another key");
+    let msg3 = "this is another message";
+    let agg = Signature::aggregate(&[sig, sk3.sign(msg3)]);
+    assert!(agg.aggregate_verify(&[pk, sk3.public_key()], &[msg, msg3]));
+}
+
+#[test]
+fn test_typed_g1() {
+    test_typed::<G1, Basic>();
+    test_typed::<G1, Aug>();
+    test_typed::<G1, Pop>();
+}
+
+#[test]
+fn test_typed_g2() {
+    test_typed::<G2, Basic>();
+    test_typed::<G2, Aug>();
+    test_typed::<G2, Pop>();
+}
+
+#[test]
+fn test_typed_pop() {
+    let sk1 = SecretKey::<G1, Pop>::new("this is the key");
+    let sk2 = SecretKey::<G1, Pop>::new("this is another key");
+    let (pk1, pk2) = (sk1.public_key(), sk2.public_key());
+    assert!(pk1.pop_verify(&sk1.pop_prove()));
+    assert!(!pk2.pop_verify(&sk1.pop_prove()));
+    assert_eq!(
+        *sk1.pop_prove().as_point(),
+        <G1 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::pop_prove("this is the key")
+    );
+
+    let msg = "this is the message";
+    let sig = Signature::aggregate(&[sk1.sign(msg), sk2.sign(msg)]);
+    assert!(sig.multisig_verify(&[pk1, pk2], msg));
+    assert!(!sig.multisig_verify(&[pk1], msg));
+}
+
+#[test]
+fn test_errors() {
+    let msg = "this is the message";
+    let (x_prime, pk) = <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen("this is the key");
+    let sig = <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x_prime, msg);
+    assert_eq!(
+        <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_verify(pk, sig, "another message"),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(
+            &[pk, pk],
+            &[msg, msg],
+            <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(&[sig, sig])
+        ),
+        Err(BlsError::DuplicateMessage)
+    );
+
+    let sk = SecretKey::<G2, Pop>::new("this is the key");
+    let mut bad = sk.public_key().to_bytes();
+    assert_eq!(
+        PublicKey::<G2, Pop>::from_bytes(&bad[..47]),
+        Err(BlsError::InvalidEncoding)
+    );
+    bad[0] ^= 0x80;
+    assert_eq!(
+        PublicKey::<G2, Pop>::from_bytes(&bad[..]),
+        Err(BlsError::InvalidEncoding)
+    );
+    assert_eq!(
+        SecretKey::<G2, Pop>::from_bytes(&[0xffu8; 32]),
+        Err(BlsError::InvalidEncoding)
+    );
+    assert_eq!(
+        sk.public_key().try_pop_verify(&sk.sign(msg)),
+        Err(BlsError::PairingCheckFailed)
+    );
+}
+
+#[test]
+fn test_key_gen() {
+    let ikm = [0x5au8; 32];
+    assert_eq!(
+        key_gen(&ikm[..31], [], KeyGenMode::Spec),
+        Err(BlsError::IkmTooShort)
+    );
+    assert_eq!(
+        key_gen("hello world (it's a secret!)", [], KeyGenMode::Legacy).unwrap(),
+        xprime_from_sk("hello world (it's a secret!)")
+    );
+    assert_ne!(
+        key_gen(&ikm[..], [], KeyGenMode::Spec).unwrap(),
+        key_gen(&ikm[..], [], KeyGenMode::Legacy).unwrap()
+    );
+    assert_ne!(
+        key_gen(&ikm[..], [], KeyGenMode::Spec).unwrap(),
+        key_gen(&ikm[..], "key info", KeyGenMode::Spec).unwrap()
+    );
+
+    let sk = SecretKey::<G1, Basic>::key_gen(&ikm[..], "key info", KeyGenMode::Spec).unwrap();
+    let (x_prime, pk) = <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::key_gen_with_info(
+        &ikm[..],
+        "key info",
+        KeyGenMode::Spec,
+    )
+    .unwrap();
+    assert_eq!(*sk.as_scalar(), x_prime);
+    assert_eq!(*sk.public_key().as_point(), pk);
+}
+
+// a point on the G1 curve that is not in the prime-order subgroup
+fn g1_non_subgroup_point() -> G1 {
+    let mut x = Fq::one();
+    loop {
+        x.add_assign(&Fq::one());
+        let mut rhs = x;
+        rhs.square();
+        rhs.mul_assign(&x);
+        rhs.add_assign(&Fq::from_repr(FqRepr::from(4)).unwrap());
+        if let Some(y) = rhs.sqrt() {
+            let pt = unsafe { transmute::g1_affine(x, y, false) };
+            if !pt.in_subgroup() {
+                return pt.into_projective();
+            }
+        }
+    }
+}
+
+fn test_identity<G>()
+where
+    G: BLSSignatureBasic<ExpandMsgXmd<Sha256>>
+        + BLSSignatureAug<ExpandMsgXmd<Sha256>>
+        + BLSSignaturePop<ExpandMsgXmd<Sha256>>,
+{
+    let pk = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::PKType::zero();
+    let sig = G::zero();
+    let msg = "any message at all";
+    assert_eq!(
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_verify(pk, sig, msg),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_verify(pk, sig, msg),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_verify(pk, sig, msg),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_pop_verify(pk, sig),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(&[pk], &[msg], sig),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(&[pk], &[msg], sig),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(&[pk], &[msg], sig),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_multisig_verify(&[pk], sig, msg),
+        Err(BlsError::IdentityPoint)
+    );
+
+    // a key and its negation sum to the identity, even if each was validated
+    let (_, pk) = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen("this is the key");
+    let mut neg_pk = pk;
+    neg_pk.negate();
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_multisig_verify_prevalidated(
+            &[pk, neg_pk],
+            sig,
+            msg
+        ),
+        Err(BlsError::IdentityPoint)
+    );
+}
+
+#[test]
+fn test_identity_g1() {
+    test_identity::<G1>();
+}
+
+#[test]
+fn test_identity_g2() {
+    test_identity::<G2>();
+}
+
+#[test]
+fn test_subgroup_checks() {
+    let bad = g1_non_subgroup_point();
+    let msg = "this is the message";
+
+    // G1 signatures
+    let (x_prime, pk) = <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen("this is the key");
+    let mut sig = <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x_prime, msg);
+    assert!(<G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        pk, sig, msg
+    ));
+    sig.add_assign(&bad);
+    assert_eq!(
+        <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_verify_prevalidated(pk, sig, msg),
+        Err(BlsError::NotInSubgroup)
+    );
+
+    // G2 signatures, G1 public keys
+    let (x_prime, mut pk) = <G2 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen("this is the key");
+    let sig = <G2 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::sign(x_prime, msg);
+    assert!(<G2 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::verify(
+        pk, sig, msg
+    ));
+    pk.add_assign(&bad);
+    assert_eq!(
+        <G2 as BLSSigCore<ExpandMsgXmd<Sha256>>>::key_validate(&pk),
+        Err(BlsError::NotInSubgroup)
+    );
+    assert_eq!(
+        <G2 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_verify(pk, sig, msg),
+        Err(BlsError::NotInSubgroup)
+    );
+    assert_eq!(
+        PublicKey::<G2, Aug>::from_point(pk).key_validate(),
+        Err(BlsError::NotInSubgroup)
+    );
+    assert_eq!(
+        PublicKey::<G2, Aug>::from_bytes(&PublicKey::<G2, Aug>::from_point(G1::zero()).to_bytes()),
+        Err(BlsError::IdentityPoint)
+    );
+}
+
+fn test_agg_lengths<G>()
+where
+    G: BLSSignatureBasic<ExpandMsgXmd<Sha256>>
+        + BLSSignatureAug<ExpandMsgXmd<Sha256>>
+        + BLSSignaturePop<ExpandMsgXmd<Sha256>>,
+{
+    let msgs = ["message 0", "message 1", "message 2"];
+    let keys: Vec<_> = (0..3)
+        .map(|i| <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+
+    let basic = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(
+        &keys
+            .iter()
+            .zip(&msgs)
+            .map(|(k, m)| <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+            .collect::<Vec<_>>(),
+    );
+    let aug = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(
+        &keys
+            .iter()
+            .zip(&msgs)
+            .map(|(k, m)| <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+            .collect::<Vec<_>>(),
+    );
+    let pop = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(
+        &keys
+            .iter()
+            .zip(&msgs)
+            .map(|(k, m)| <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+            .collect::<Vec<_>>(),
+    );
+
+    // sanity check: matching lengths verify
+    assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::aggregate_verify(&pks, &msgs, basic));
+    assert!(<G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::aggregate_verify(&pks, &msgs, aug));
+    assert!(<G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::aggregate_verify(&pks, &msgs, pop));
+
+    // extra key, extra message: both must be rejected, not truncated
+    let cases: [(&[_], &[&str]); 2] = [(&pks[..], &msgs[..2]), (&pks[..2], &msgs[..])];
+    for &(p, m) in cases.iter() {
+        assert_eq!(
+            <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(p, m, basic),
+            Err(BlsError::LengthMismatch)
+        );
+        assert_eq!(
+            <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_aggregate_verify_prevalidated(
+                p, m, basic
+            ),
+            Err(BlsError::LengthMismatch)
+        );
+        assert_eq!(
+            <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(p, m, aug),
+            Err(BlsError::LengthMismatch)
+        );
+        assert_eq!(
+            <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_aggregate_verify_prevalidated(
+                p, m, aug
+            ),
+            Err(BlsError::LengthMismatch)
+        );
+        assert_eq!(
+            <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(p, m, pop),
+            Err(BlsError::LengthMismatch)
+        );
+        assert_eq!(
+            <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_aggregate_verify_prevalidated(
+                p, m, pop
+            ),
+            Err(BlsError::LengthMismatch)
+        );
+        assert!(!<G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::aggregate_verify(p, m, aug));
+    }
+
+    // empty input: rejected regardless of the signature
+    let no_msgs: [&str; 0] = [];
+    for &sig in [basic, G::zero()].iter() {
+        assert_eq!(
+            <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(
+                &[],
+                &no_msgs,
+                sig
+            ),
+            Err(BlsError::EmptyInput)
+        );
+        assert_eq!(
+            <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(&[], &no_msgs, sig),
+            Err(BlsError::EmptyInput)
+        );
+        assert_eq!(
+            <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_aggregate_verify(&[], &no_msgs, sig),
+            Err(BlsError::EmptyInput)
+        );
+        assert_eq!(
+            <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::try_core_aggregate_verify_prevalidated(
+                &[],
+                &no_msgs,
+                sig,
+                "any ciphersuite"
+            ),
+            Err(BlsError::EmptyInput)
+        );
+        assert_eq!(
+            <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_multisig_verify(&[], sig, msgs[0]),
+            Err(BlsError::EmptyInput)
+        );
+    }
+}
+
+#[test]
+fn test_agg_lengths_g1() {
+    test_agg_lengths::<G1>();
+}
+
+#[test]
+fn test_agg_lengths_g2() {
+    test_agg_lengths::<G2>();
+}
+
+fn test_batch_verify<G>()
+where
+    G: BLSSignatureBasic<ExpandMsgXmd<Sha256>>
+        + BLSSignatureAug<ExpandMsgXmd<Sha256>>
+        + BLSSignaturePop<ExpandMsgXmd<Sha256>>,
+{
+    // independent signatures: the last two are on the same message
+    let msgs = ["message 0", "message 1", "message 2", "message 2"];
+    let keys: Vec<_> = (0..4)
+        .map(|i| <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let basic: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    let aug: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    let pop: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+
+    assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::batch_verify(&pks, &msgs, &basic));
+    assert!(<G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::batch_verify(
+        &pks, &msgs, &aug
+    ));
+    assert!(<G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::batch_verify(
+        &pks, &msgs, &pop
+    ));
+    assert!(<G as BLSSigCore<ExpandMsgXmd<Sha256>>>::core_batch_verify(
+        &mut thread_rng(),
+        &pks,
+        &msgs,
+        &basic,
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::CSUITE,
+    ));
+    // a batch of one is the same as a single verification
+    assert!(
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::batch_verify(
+            &pks[..1],
+            &msgs[..1],
+            &basic[..1]
+        )
+    );
+
+    // signatures from the wrong scheme fail
+    assert_eq!(
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_batch_verify(&pks, &msgs, &aug),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert!(!<G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::batch_verify(
+        &pks, &msgs, &pop
+    ));
+
+    // one bad signature fails the batch, even if the sum of all signatures is unchanged
+    let mut swapped = basic.clone();
+    swapped.swap(0, 1);
+    assert!(!<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::batch_verify(&pks, &msgs, &swapped));
+    let mut shifted = basic.clone();
+    let delta = <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(keys[0].0, "delta");
+    shifted[0].add_assign(&delta);
+    shifted[1].sub_assign(&delta);
+    assert_eq!(
+        <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(&shifted),
+        <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(&basic)
+    );
+    assert_eq!(
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_batch_verify(&pks, &msgs, &shifted),
+        Err(BlsError::PairingCheckFailed)
+    );
+
+    // lengths must match and be nonzero, and keys are validated
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_batch_verify(&pks, &msgs, &pop[1..]),
+        Err(BlsError::LengthMismatch)
+    );
+    assert_eq!(
+        <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::try_batch_verify(&pks[1..], &msgs, &aug),
+        Err(BlsError::LengthMismatch)
+    );
+    assert_eq!(
+        <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::try_batch_verify_prevalidated(
+            &[],
+            &[] as &[&[u8]],
+            &[]
+        ),
+        Err(BlsError::EmptyInput)
+    );
+    let mut bad_pks = pks.clone();
+    bad_pks[2] = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::PKType::zero();
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_batch_verify(&bad_pks, &msgs, &pop),
+        Err(BlsError::IdentityPoint)
+    );
+}
+
+#[test]
+fn test_batch_verify_g1() {
+    test_batch_verify::<G1>();
+
+    // signatures outside the subgroup are rejected
+    let msgs = ["message 0", "message 1"];
+    let keys: Vec<_> = (0..2)
+        .map(|i| <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let mut sigs: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G1 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    sigs[1].add_assign(&g1_non_subgroup_point());
+    assert_eq!(
+        <G1 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_batch_verify(&pks, &msgs, &sigs),
+        Err(BlsError::NotInSubgroup)
+    );
+}
+
+#[test]
+fn test_batch_verify_g2() {
+    test_batch_verify::<G2>();
+}
+
+fn test_batch_find_invalid<G>()
+where
+    G: BLSSignatureBasic<ExpandMsgXmd<Sha256>>
+        + BLSSignatureAug<ExpandMsgXmd<Sha256>>
+        + BLSSignaturePop<ExpandMsgXmd<Sha256>>,
+{
+    let msgs: Vec<_> = (0..13).map(|i| format!("message {}", i)).collect();
+    let keys: Vec<_> = (0..13)
+        .map(|i| <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let mut pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let mut sigs: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::batch_find_invalid(&pks, &msgs, &sigs),
+        Ok(vec![])
+    );
+
+    // a wrong message, a swapped pair, and a signature from another scheme
+    sigs[2] = <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::sign(keys[2].0, "not message 2");
+    sigs.swap(7, 8);
+    sigs[12] = <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(keys[12].0, &msgs[12]);
+    let agg = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(&sigs);
+    assert!(!<G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::aggregate_verify(&pks, &msgs, agg));
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::batch_find_invalid(&pks, &msgs, &sigs),
+        Ok(vec![2, 7, 8, 12])
+    );
+    assert_eq!(
+        <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::core_batch_find_invalid_prevalidated(
+            &mut thread_rng(),
+            &pks,
+            &msgs,
+            &sigs,
+            <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::CSUITE,
+        ),
+        Ok(vec![2, 7, 8, 12])
+    );
+
+    // invalid public keys are reported without a pairing check
+    pks[0] = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::PKType::zero();
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::batch_find_invalid(&pks, &msgs, &sigs),
+        Ok(vec![0, 2, 7, 8, 12])
+    );
+
+    // augmented signatures, all of them bad
+    let aug: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    assert_eq!(
+        <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::batch_find_invalid(&pks, &msgs, &aug),
+        Ok(vec![])
+    );
+    assert_eq!(
+        <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::batch_find_invalid(&pks, &msgs[..1], &aug),
+        Err(BlsError::LengthMismatch)
+    );
+    let mut rotated = aug.clone();
+    rotated.rotate_left(1);
+    assert_eq!(
+        <G as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::batch_find_invalid_prevalidated(
+            &pks, &msgs, &rotated
+        ),
+        Ok((0..13).collect())
+    );
+}
+
+#[test]
+fn test_batch_find_invalid_g1() {
+    test_batch_find_invalid::<G1>();
+
+    // signatures outside the subgroup are reported without a pairing check
+    let msgs = ["message 0", "message 1", "message 2"];
+    let keys: Vec<_> = (0..3)
+        .map(|i| <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let mut sigs: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    sigs[1].add_assign(&g1_non_subgroup_point());
+    assert_eq!(
+        <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::batch_find_invalid(&pks, &msgs, &sigs),
+        Ok(vec![1])
+    );
+}
+
+#[test]
+fn test_batch_find_invalid_g2() {
+    test_batch_find_invalid::<G2>();
+}
+
+fn test_multi_miller_loop_help() {
+    let pairs: Vec<_> = (0..37)
+        .map(|i| {
+            let (x_prime, pk) =
+                <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i));
+            let sig = <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x_prime, "message");
+            (sig.into_affine().prepare(), pk.into_affine().prepare())
+        })
+        .collect();
+    let pqz: Vec<_> = pairs.iter().map(|(p, q)| (p, q)).collect();
+    assert_eq!(_multi_miller_loop(&pqz[..]), Bls12::miller_loop(&pqz[..]));
+    assert_eq!(_multi_miller_loop(&pqz[..1]), Bls12::miller_loop(&pqz[..1]));
+
+    let msgs: Vec<_> = (0..9).map(|i| format!("message {}", i)).collect();
+    let keys: Vec<_> = (0..9)
+        .map(|i| <G2 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let sigs: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| <G2 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(k.0, m))
+        .collect();
+    let agg = <G2 as BLSSigCore<ExpandMsgXmd<Sha256>>>::aggregate(&sigs);
+    assert!(<G2 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::aggregate_verify(&pks, &msgs, agg));
+    assert!(
+        !<G2 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::aggregate_verify(
+            &pks[1..],
+            &msgs[..8],
+            agg
+        )
+    );
+    assert_eq!(
+        <G2 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::batch_find_invalid(
+            &pks,
+            &msgs,
+            &[&sigs[..4], &sigs[5..6], &sigs[5..]].concat()
+        ),
+        Ok(vec![4])
+    );
+}
+
+// the chunked Miller loop gives exactly the same result as one big Miller loop
+#[test]
+fn test_multi_miller_loop() {
+    test_multi_miller_loop_help();
+
+    // force several chunks even on a single-core machine
+    #[cfg(feature = "parallel")]
+    ::rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(test_multi_miller_loop_help);
+}
+
+#[test]
+fn test_encoding() {
+    let (x_prime, pk) = <G1 as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen("this is the key");
+    let sig = <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x_prime, "this is the message");
+    assert_eq!(sig.to_compressed().len(), 48);
+    assert_eq!(sig.to_uncompressed().len(), 96);
+    assert_eq!(pk.to_compressed().len(), 96);
+    assert_eq!(pk.to_uncompressed().len(), 192);
+    assert_eq!(G2::from_compressed(&pk.to_compressed()).unwrap(), pk);
+    assert_eq!(G2::from_uncompressed(&pk.to_uncompressed()).unwrap(), pk);
+
+    // compressed and uncompressed forms have different flags
+    let mut bytes = sig.to_compressed();
+    assert_eq!(bytes[0] & 0x80, 0x80);
+    assert_eq!(G1::from_compressed(&bytes).unwrap(), sig);
+    bytes[0] &= 0x7f;
+    assert_eq!(G1::from_compressed(&bytes), Err(BlsError::InvalidEncoding));
+    let mut bytes = sig.to_uncompressed();
+    bytes[0] |= 0x80;
+    assert_eq!(
+        G1::from_uncompressed(&bytes),
+        Err(BlsError::InvalidEncoding)
+    );
+
+    // the identity has exactly one encoding
+    let mut bytes = G1::zero().to_compressed();
+    assert_eq!(bytes[0], 0xc0);
+    assert!(bytes[1..].iter().all(|b| *b == 0));
+    assert_eq!(G1::from_compressed(&bytes).unwrap(), G1::zero());
+    bytes[0] |= 0x20;
+    assert_eq!(G1::from_compressed(&bytes), Err(BlsError::InvalidEncoding));
+    bytes[0] = 0xc0;
+    bytes[47] = 1;
+    assert_eq!(G1::from_compressed(&bytes), Err(BlsError::InvalidEncoding));
+    assert_eq!(
+        PublicKey::<G2, Basic>::from_compressed(&G1::zero().to_compressed()),
+        Err(BlsError::IdentityPoint)
+    );
+
+    // x-coordinate equal to the field modulus is not canonical
+    let mut bytes = [0u8; 48];
+    Fq::char().write_be(&mut bytes[..]).unwrap();
+    bytes[0] |= 0x80;
+    assert_eq!(G1::from_compressed(&bytes), Err(BlsError::InvalidEncoding));
+
+    // points outside the prime-order subgroup are rejected
+    let bad = g1_non_subgroup_point();
+    assert_eq!(
+        G1::from_compressed(&bad.to_compressed()),
+        Err(BlsError::NotInSubgroup)
+    );
+    assert_eq!(
+        G1::from_uncompressed(&bad.to_uncompressed()),
+        Err(BlsError::NotInSubgroup)
+    );
+
+    // secret keys are 32-byte big-endian integers in [1, r - 1]
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    assert_eq!(sk_from_bytes(&one).unwrap(), Fr::one());
+    assert_eq!(sk_to_bytes(&Fr::one()), one);
+    assert_eq!(sk_from_bytes(&sk_to_bytes(&x_prime)).unwrap(), x_prime);
+    assert_eq!(sk_from_bytes(&[0u8; 32]), Err(BlsError::InvalidEncoding));
+    let mut bytes = [0u8; 32];
+    Fr::char().write_be(&mut bytes[..]).unwrap();
+    assert_eq!(sk_from_bytes(&bytes), Err(BlsError::InvalidEncoding));
+}
+
+#[cfg(feature = "serde")]
+fn hex_to_bytes(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+// round-trip signatures from test-vectors/<name> through JSON and bincode
+#[cfg(feature = "serde")]
+fn test_serde_vecs<G, S>(name: &str)
+where
+    G: PointEncoding<Scalar = Fr> + SerDes,
+    S: Scheme<G>,
+{
+    use bincode;
+    use serde_json;
+    use std::fs;
+    use std::path::PathBuf;
+
+    let dir: PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "test-vectors", name]
+        .iter()
+        .collect();
+    for entry in fs::read_dir(dir).unwrap() {
+        let contents = fs::read_to_string(entry.unwrap().path()).unwrap();
+        // one vector per file is plenty; signing is the slow part
+        for line in contents.lines().take(1) {
+            let fields: Vec<_> = line.split_whitespace().map(hex_to_bytes).collect();
+            let (msg, expect) = (&fields[0], &fields[2]);
+            let expect_hex = line.split_whitespace().nth(2).unwrap();
+            let sk = SecretKey::<G, S>::new(&fields[1]);
+            let pk = sk.public_key();
+            let sig = sk.sign(msg);
+            assert_eq!(sig.to_compressed().as_ref(), &expect[..]);
+
+            let json = serde_json::to_string(&sig).unwrap();
+            assert_eq!(json, format!("\"{}\"", expect_hex));
+            let sig2: Signature<G, S> = serde_json::from_str(&json).unwrap();
+            assert_eq!(sig, sig2);
+            let sig2: Signature<G, S> = serde_json::from_str(&json.to_uppercase()).unwrap();
+            assert_eq!(sig, sig2);
+
+            let bin = bincode::serialize(&sig).unwrap();
+            assert!(bin.ends_with(&expect[..]));
+            let sig2: Signature<G, S> = bincode::deserialize(&bin).unwrap();
+            assert_eq!(sig, sig2);
+
+            let pk2: PublicKey<G, S> =
+                serde_json::from_str(&serde_json::to_string(&pk).unwrap()).unwrap();
+            assert_eq!(pk, pk2);
+            let pk2: PublicKey<G, S> =
+                bincode::deserialize(&bincode::serialize(&pk).unwrap()).unwrap();
+            assert_eq!(pk, pk2);
+            assert!(pk2.verify(&sig2, msg));
+
+            #[cfg(feature = "serde-secret-keys")]
+            {
+                let json = serde_json::to_string(&sk).unwrap();
+                let sk2: SecretKey<G, S> = serde_json::from_str(&json).unwrap();
+                assert_eq!(sk, sk2);
+                let sk2: SecretKey<G, S> =
+                    bincode::deserialize(&bincode::serialize(&sk).unwrap()).unwrap();
+                assert_eq!(sk, sk2);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_g1() {
+    test_serde_vecs::<G1, Basic>("sig_g1_basic");
+    test_serde_vecs::<G1, Aug>("sig_g1_aug");
+    test_serde_vecs::<G1, Pop>("sig_g1_pop");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_g2() {
+    test_serde_vecs::<G2, Basic>("sig_g2_basic");
+    test_serde_vecs::<G2, Aug>("sig_g2_aug");
+    test_serde_vecs::<G2, Pop>("sig_g2_pop");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_invalid() {
+    use bincode;
+    use serde_json;
+
+    // identity public key: well-formed encoding, but fails key_validate
+    let ident = format!("\"c0{}\"", "00".repeat(47));
+    assert!(serde_json::from_str::<PublicKey<G2, Basic>>(&ident).is_err());
+    assert!(serde_json::from_str::<Signature<G1, Basic>>(&ident).is_ok());
+
+    // not in the subgroup
+    let bad = g1_non_subgroup_point().to_compressed();
+    assert!(
+        bincode::deserialize::<Signature<G1, Basic>>(&bincode::serialize(&bad[..]).unwrap())
+            .is_err()
+    );
+
+    // wrong length, odd length, and non-hex input
+    let sig = SecretKey::<G1, Basic>::new("this is the key").sign("this is the message");
+    let json = serde_json::to_string(&sig).unwrap();
+    assert!(serde_json::from_str::<Signature<G1, Basic>>(&json.replacen("\"", "\"00", 1)).is_err());
+    assert!(serde_json::from_str::<Signature<G1, Basic>>(&json.replacen("\"", "\"0", 1)).is_err());
+    assert!(serde_json::from_str::<Signature<G1, Basic>>(&json.replacen("\"", "\"zz", 1)).is_err());
+    assert!(serde_json::from_str::<Signature<G2, Basic>>(&json).is_err());
+}
+
+ciphersuite! {
+    /// Basic scheme with SHA-512 and a custom tag
+    pub TestG2Sha512Basic: G2, ExpandMsgXmd<Sha512>, Basic, b"TEST_BLS_SIG_BLS12381G2_XMD:SHA-512_SSWU_RO_NUL_"
+}
+
+ciphersuite! {
+    /// Proof of possession scheme with SHA-512 and custom tags
+    pub TestG1Sha512Pop: G1, ExpandMsgXmd<Sha512>, Pop, b"TEST_BLS_SIG_BLS12381G1_XMD:SHA-512_SSWU_RO_POP_",
+    pop: b"TEST_BLS_POP_BLS12381G1_XMD:SHA-512_SSWU_RO_POP_"
+}
+
+ciphersuite! {
+    /// Basic scheme with SHA-512 that reuses the SHA-256 Basic suite's tag
+    pub TestG1Sha512SameDstBasic: G1, ExpandMsgXmd<Sha512>, Basic, G1XmdSha256Basic::DST
+}
+
+// a standard ciphersuite must agree with the corresponding scheme trait
+fn test_std_suite<C: Ciphersuite>() {
+    let msgs = ["message 0", "message 1"];
+    let keys: Vec<_> = (0..2)
+        .map(|i| C::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let sigs: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| C::sign(k.0, m))
+        .collect();
+    for ((pk, sig), msg) in pks.iter().zip(&sigs).zip(&msgs) {
+        assert!(C::verify(*pk, *sig, msg));
+        assert!(!C::verify(*pk, *sig, "this is not the message"));
+    }
+    let agg = <C::Group as BLSSigCore<C::Expand>>::aggregate(&sigs);
+    assert!(C::aggregate_verify(&pks, &msgs, agg));
+    assert_eq!(
+        C::try_aggregate_verify(&pks[..1], &msgs, agg),
+        Err(BlsError::LengthMismatch)
+    );
+    assert!(C::batch_verify(&pks, &msgs, &sigs));
+    assert!(!C::batch_verify(&pks, &msgs, &[sigs[1], sigs[0]]));
+    assert_eq!(C::batch_find_invalid(&pks, &msgs, &sigs), Ok(vec![]));
+    assert_eq!(
+        C::batch_find_invalid(&pks, &msgs, &[sigs[0], sigs[0]]),
+        Ok(vec![1])
+    );
+
+    // the typed wrappers accept any ciphersuite as a scheme
+    let sk = SecretKey::<C::Group, C>::new("key number 0");
+    assert_eq!(sk.sign(msgs[0]).as_point(), &sigs[0]);
+    assert!(sk.public_key().verify(&sk.sign(msgs[0]), msgs[0]));
+    let sk1 = SecretKey::<C::Group, C>::new("key number 1");
+    let typed_sigs = [sk.sign(msgs[0]), sk1.sign(msgs[1])];
+    let typed_pks = [sk.public_key(), sk1.public_key()];
+    assert!(Signature::batch_verify(&typed_sigs, &typed_pks, &msgs));
+    assert!(!Signature::batch_verify(
+        &typed_sigs,
+        &typed_pks,
+        &["message 1", "message 0"]
+    ));
+    assert_eq!(
+        Signature::batch_find_invalid(&typed_sigs, &typed_pks, &[msgs[0], "message 2"]),
+        Ok(vec![1])
+    );
+}
+
+#[test]
+fn test_ciphersuites() {
+    test_std_suite::<G1XmdSha256Basic>();
+    test_std_suite::<G1XmdSha256Aug>();
+    test_std_suite::<G1XmdSha256Pop>();
+    test_std_suite::<G2XmdSha256Basic>();
+    test_std_suite::<G2XmdSha256Aug>();
+    test_std_suite::<G2XmdSha256Pop>();
+    test_std_suite::<G1XofShake256Basic>();
+    test_std_suite::<G1XofShake256Aug>();
+    test_std_suite::<G1XofShake256Pop>();
+    test_std_suite::<G2XofShake256Basic>();
+    test_std_suite::<G2XofShake256Aug>();
+    test_std_suite::<G2XofShake256Pop>();
+    test_std_suite::<TestG2Sha512Basic>();
+    test_std_suite::<TestG1Sha512Pop>();
+
+    let msg = "this is the message";
+    let (x1, pk1) = G1XmdSha256Basic::keygen("this is the key");
+    assert_eq!(
+        G1XmdSha256Basic::sign(x1, msg),
+        <G1 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x1, msg)
+    );
+    assert_eq!(
+        G1XmdSha256Aug::sign(x1, msg),
+        <G1 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::sign(x1, msg)
+    );
+    assert_eq!(
+        G1XmdSha256Pop::pop_prove(x1),
+        <G1 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::pop_prove("this is the key")
+    );
+    let (x2, pk2) = G2XmdSha256Basic::keygen("this is the key");
+    assert_eq!(
+        G2XmdSha256Basic::sign(x2, msg),
+        <G2 as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x2, msg)
+    );
+    assert_eq!(
+        G2XmdSha256Aug::sign(x2, msg),
+        <G2 as BLSSignatureAug<ExpandMsgXmd<Sha256>>>::sign(x2, msg)
+    );
+    assert_eq!(
+        G2XmdSha256Pop::pop_prove(x2),
+        <G2 as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::pop_prove("this is the key")
+    );
+
+    // a custom tag and hash give a different, incompatible signature
+    let sig = TestG2Sha512Basic::sign(x2, msg);
+    assert!(TestG2Sha512Basic::verify(pk2, sig, msg));
+    assert!(!G2XmdSha256Basic::verify(pk2, sig, msg));
+    assert_eq!(
+        <G2XmdSha256Basic as Ciphersuite>::try_verify(pk2, sig, msg),
+        Err(BlsError::PairingCheckFailed)
+    );
+
+    // XOF suites are incompatible with the corresponding XMD suites
+    let sig = G1XofShake256Basic::sign(x1, msg);
+    assert!(G1XofShake256Basic::verify(pk1, sig, msg));
+    assert!(!G1XmdSha256Basic::verify(pk1, sig, msg));
+    let proof = G2XofShake256Pop::pop_prove(x2);
+    assert!(G2XofShake256Pop::pop_verify(pk2, proof));
+    assert!(!G2XmdSha256Pop::pop_verify(pk2, proof));
+
+    // custom proof of possession and multisignature
+    let (x3, pk3) = TestG1Sha512Pop::keygen("this is another key");
+    let proof = TestG1Sha512Pop::pop_prove(x1);
+    assert!(TestG1Sha512Pop::pop_verify(pk1, proof));
+    assert!(!G1XmdSha256Pop::pop_verify(pk1, proof));
+    assert!(!TestG1Sha512Pop::pop_verify(pk3, proof));
+    let multisig = <G1 as BLSSigCore<ExpandMsgXmd<Sha512>>>::aggregate(&[
+        TestG1Sha512Pop::sign(x1, msg),
+        TestG1Sha512Pop::sign(x3, msg),
+    ]);
+    assert!(TestG1Sha512Pop::multisig_verify(&[pk1, pk3], multisig, msg));
+    assert!(!TestG1Sha512Pop::multisig_verify(&[pk1], multisig, msg));
+    assert_eq!(
+        <TestG1Sha512Pop as PopCiphersuite>::try_multisig_verify(&[], multisig, msg),
+        Err(BlsError::EmptyInput)
+    );
+}
+
+// signing and verifying with a hashed message must agree with the unhashed versions
+fn test_hashed_unaug<C: Ciphersuite>()
+where
+    C::Scheme: Unaugmented,
+{
+    let msgs = ["message 0", "message 1"];
+    let hashed: Vec<_> = msgs
+        .iter()
+        .map(|m| HashedMessage::<C::Group, C>::new(m))
+        .collect();
+    let keys: Vec<_> = (0..2)
+        .map(|i| C::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let sigs: Vec<_> = keys
+        .iter()
+        .zip(&hashed)
+        .map(|(k, h)| C::sign_hashed(k.0, h))
+        .collect();
+    for (((k, pk), sig), (msg, h)) in keys
+        .iter()
+        .zip(&pks)
+        .zip(&sigs)
+        .zip(msgs.iter().zip(&hashed))
+    {
+        assert_eq!(*sig, C::sign(k.0, msg));
+        assert!(C::verify_hashed(*pk, *sig, h));
+        assert!(C::verify(*pk, *sig, msg));
+    }
+    assert!(!C::verify_hashed(pks[0], sigs[0], &hashed[1]));
+    assert_eq!(
+        C::try_verify_hashed(PkT::<C>::zero(), sigs[0], &hashed[0]),
+        Err(BlsError::IdentityPoint)
+    );
+
+    let agg = <C::Group as BLSSigCore<C::Expand>>::aggregate(&sigs);
+    assert!(C::aggregate_verify_hashed(&pks, &hashed, agg));
+    let hashed_refs: Vec<_> = hashed.iter().collect();
+    assert!(C::aggregate_verify_hashed(&pks, &hashed_refs, agg));
+    assert!(!C::aggregate_verify_hashed(
+        &pks,
+        &[&hashed[1], &hashed[0]],
+        agg
+    ));
+    assert_eq!(
+        C::try_aggregate_verify_hashed(&pks[..1], &hashed, agg),
+        Err(BlsError::LengthMismatch)
+    );
+    assert_eq!(
+        C::try_aggregate_verify_hashed::<&HashedMessage<C::Group, C>>(&[], &[], agg),
+        Err(BlsError::EmptyInput)
+    );
+
+    // typed wrappers
+    let sk = SecretKey::<C::Group, C>::new("key number 0");
+    let sig = sk.sign_hashed(&hashed[0]);
+    assert_eq!(sig, sk.sign(msgs[0]));
+    assert!(sk.public_key().verify_hashed(&sig, &hashed[0]));
+    assert!(!sk.public_key().verify_hashed(&sig, &hashed[1]));
+    let sk1 = SecretKey::<C::Group, C>::new("key number 1");
+    let typed_agg = Signature::aggregate(&[sig, sk1.sign_hashed(&hashed[1])]);
+    assert!(typed_agg.aggregate_verify_hashed(&[sk.public_key(), sk1.public_key()], &hashed));
+}
+
+// under Aug, the hash depends on the signer's public key
+fn test_hashed_aug<C: Ciphersuite<Scheme = Aug>>() {
+    let msg = "this is the message";
+    let (x1, pk1) = C::keygen("key number 0");
+    let (x2, pk2) = C::keygen("key number 1");
+    let h1 = HashedMessage::<C::Group, C>::new_aug(&pk1, msg);
+    let h2 = HashedMessage::<C::Group, C>::new_aug(&pk2, msg);
+    assert_ne!(h1, h2);
+
+    let sig1 = C::sign_hashed(x1, &h1);
+    let sig2 = C::sign_hashed(x2, &h2);
+    assert_eq!(sig1, C::sign(x1, msg));
+    assert!(C::verify_hashed(pk1, sig1, &h1));
+    assert!(C::verify(pk1, sig1, msg));
+    // a message hashed under the wrong public key does not verify
+    assert!(!C::verify_hashed(pk2, sig1, &h1));
+    assert!(!C::verify_hashed(pk1, sig1, &h2));
+
+    // the same message from both signers is fine under Aug
+    let agg = <C::Group as BLSSigCore<C::Expand>>::aggregate(&[sig1, sig2]);
+    assert!(C::aggregate_verify_hashed(&[pk1, pk2], &[&h1, &h2], agg));
+    assert!(C::aggregate_verify(&[pk1, pk2], &[msg, msg], agg));
+    assert!(!C::aggregate_verify_hashed(&[pk1, pk2], &[&h2, &h1], agg));
+}
+
+#[test]
+fn test_hashed_message() {
+    test_hashed_unaug::<G1XmdSha256Basic>();
+    test_hashed_unaug::<G1XmdSha256Pop>();
+    test_hashed_unaug::<G2XmdSha256Basic>();
+    test_hashed_unaug::<G2XofShake256Pop>();
+    test_hashed_aug::<G1XmdSha256Aug>();
+    test_hashed_aug::<G2XmdSha256Aug>();
+
+    // Basic rejects repeated messages, Pop does not
+    let msg = "this is the message";
+    let h = HashedMessage::<G1, G1XmdSha256Basic>::new(msg);
+    let (x1, pk1) = G1XmdSha256Basic::keygen("key number 0");
+    let (x2, pk2) = G1XmdSha256Basic::keygen("key number 1");
+    let mut agg = G1XmdSha256Basic::sign_hashed(x1, &h);
+    agg.add_assign(&G1XmdSha256Basic::sign_hashed(x2, &h));
+    assert_eq!(
+        G1XmdSha256Basic::try_aggregate_verify_hashed(&[pk1, pk2], &[&h, &h], agg),
+        Err(BlsError::DuplicateMessage)
+    );
+    let h = HashedMessage::<G2, G2XmdSha256Pop>::new(msg);
+    let (x1, pk1) = G2XmdSha256Pop::keygen("key number 0");
+    let (x2, pk2) = G2XmdSha256Pop::keygen("key number 1");
+    let mut agg = G2XmdSha256Pop::sign_hashed(x1, &h);
+    agg.add_assign(&G2XmdSha256Pop::sign_hashed(x2, &h));
+    assert!(G2XmdSha256Pop::aggregate_verify_hashed(
+        &[pk1, pk2],
+        &[&h, &h],
+        agg
+    ));
+    assert!(G2XmdSha256Pop::multisig_verify_hashed(&[pk1, pk2], agg, &h));
+    assert!(!G2XmdSha256Pop::multisig_verify_hashed(&[pk1], agg, &h));
+    assert_eq!(
+        G2XmdSha256Pop::try_multisig_verify_hashed(&[], agg, &h),
+        Err(BlsError::EmptyInput)
+    );
+}
+
+#[test]
+fn test_hash_cache() {
+    let mut cache = HashCache::<G1>::new(2);
+    assert!(cache.is_empty());
+    assert_eq!(cache.capacity(), 2);
+
+    let h0 = cache.hashed::<G1XmdSha256Basic, _>("message 0");
+    assert_eq!(h0, HashedMessage::new("message 0"));
+    assert!(cache.contains::<G1XmdSha256Basic, _>("message 0"));
+    // the same message under another tag is a different entry
+    assert!(!cache.contains::<G1XmdSha256Pop, _>("message 0"));
+    let h0pop = cache.hashed::<G1XmdSha256Pop, _>("message 0");
+    assert_ne!(h0.as_point(), h0pop.as_point());
+    assert_eq!(cache.len(), 2);
+
+    // touching message 0 makes the Pop entry the least recently used
+    assert_eq!(cache.hashed::<G1XmdSha256Basic, _>("message 0"), h0);
+    cache.hashed::<G1XmdSha256Basic, _>("message 1");
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains::<G1XmdSha256Basic, _>("message 0"));
+    assert!(cache.contains::<G1XmdSha256Basic, _>("message 1"));
+    assert!(!cache.contains::<G1XmdSha256Pop, _>("message 0"));
+
+    // Aug entries are keyed by pk || msg
+    let (x, pk) = G1XmdSha256Aug::keygen("this is the key");
+    let h = cache.hashed_aug::<G1XmdSha256Aug, _>(&pk, "message 0");
+    assert_eq!(h, HashedMessage::new_aug(&pk, "message 0"));
+    assert!(G1XmdSha256Aug::verify(
+        pk,
+        G1XmdSha256Aug::sign_hashed(x, &h),
+        "message 0"
+    ));
+    assert!(!cache.contains::<G1XmdSha256Aug, _>("message 0"));
+    assert_eq!(cache.len(), 2);
+
+    // a suite with the same tag but another expander does not share entries
+    assert!(cache.contains::<G1XmdSha256Basic, _>("message 1"));
+    assert!(!cache.contains::<TestG1Sha512SameDstBasic, _>("message 1"));
+    let h1 = cache.hashed::<TestG1Sha512SameDstBasic, _>("message 1");
+    assert_eq!(h1, HashedMessage::new("message 1"));
+    assert_ne!(
+        h1.as_point(),
+        HashedMessage::<G1, G1XmdSha256Basic>::new("message 1").as_point()
+    );
+
+    cache.clear();
+    assert!(cache.is_empty());
+
+    // zero capacity hashes without caching
+    let mut cache = HashCache::<G2>::new(0);
+    let h = cache.hashed::<G2XmdSha256Basic, _>("message 0");
+    assert_eq!(h, HashedMessage::new("message 0"));
+    assert!(cache.is_empty());
+}
+
+// verifying with a prepared public key must agree with the unprepared versions
+fn test_prepared_suite<C: Ciphersuite>() {
+    let msgs = ["message 0", "message 1"];
+    let keys: Vec<_> = (0..2)
+        .map(|i| C::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let ppks: Vec<_> = pks
+        .iter()
+        .map(|pk| PreparedPublicKey::<C::Group, C>::new(*pk).unwrap())
+        .collect();
+    let sigs: Vec<_> = keys
+        .iter()
+        .zip(&msgs)
+        .map(|(k, m)| C::sign(k.0, m))
+        .collect();
+    for ((ppk, sig), msg) in ppks.iter().zip(&sigs).zip(&msgs) {
+        assert!(C::verify_prepared(ppk, *sig, msg));
+        assert!(!C::verify_prepared(ppk, *sig, "this is not the message"));
+    }
+    assert_eq!(
+        C::try_verify_prepared(&ppks[1], sigs[0], msgs[0]),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        PreparedPublicKey::<C::Group, C>::new(PkT::<C>::zero()),
+        Err(BlsError::IdentityPoint)
+    );
+
+    let agg = <C::Group as BLSSigCore<C::Expand>>::aggregate(&sigs);
+    assert!(C::aggregate_verify_prepared(&ppks, &msgs, agg));
+    assert!(C::aggregate_verify_prepared(
+        &[&ppks[0], &ppks[1]],
+        &msgs,
+        agg
+    ));
+    assert!(!C::aggregate_verify_prepared(
+        &[&ppks[1], &ppks[0]],
+        &msgs,
+        agg
+    ));
+    assert_eq!(
+        C::try_aggregate_verify_prepared(&ppks[..1], &msgs, agg),
+        Err(BlsError::LengthMismatch)
+    );
+
+    // typed wrappers
+    let sk = SecretKey::<C::Group, C>::new("key number 0");
+    let ppk = PreparedPublicKey::from_public_key(&sk.public_key()).unwrap();
+    assert_eq!(ppk, ppks[0]);
+    assert_eq!(ppk.as_affine(), &pks[0].into_affine());
+    assert!(ppk.verify(&sk.sign(msgs[0]), msgs[0]));
+    assert!(!ppk.verify(&sk.sign(msgs[0]), msgs[1]));
+    let typed_agg = Signature::<C::Group, C>::from_point(agg);
+    assert!(typed_agg.aggregate_verify_prepared(&ppks, &msgs));
+}
+
+#[test]
+fn test_prepared_public_key() {
+    test_prepared_suite::<G1XmdSha256Basic>();
+    test_prepared_suite::<G1XmdSha256Aug>();
+    test_prepared_suite::<G1XmdSha256Pop>();
+    test_prepared_suite::<G2XmdSha256Basic>();
+    test_prepared_suite::<G2XmdSha256Aug>();
+    test_prepared_suite::<G2XofShake256Pop>();
+
+    // Basic still rejects repeated messages
+    let msg = "this is the message";
+    let (x1, pk1) = G2XmdSha256Basic::keygen("key number 0");
+    let (x2, pk2) = G2XmdSha256Basic::keygen("key number 1");
+    let ppks = [
+        PreparedPublicKey::new(pk1).unwrap(),
+        PreparedPublicKey::new(pk2).unwrap(),
+    ];
+    let mut agg = G2XmdSha256Basic::sign(x1, msg);
+    agg.add_assign(&G2XmdSha256Basic::sign(x2, msg));
+    assert_eq!(
+        G2XmdSha256Basic::try_aggregate_verify_prepared(&ppks, &[msg, msg], agg),
+        Err(BlsError::DuplicateMessage)
+    );
+
+    // with a hashed message, too; under Aug the message is hashed with the key
+    let (x, pk) = G1XmdSha256Aug::keygen("this is the key");
+    let ppk = PreparedPublicKey::<G1, G1XmdSha256Aug>::new(pk).unwrap();
+    let h = HashedMessage::new_aug(&pk, msg);
+    let sig = G1XmdSha256Aug::sign(x, msg);
+    assert!(G1XmdSha256Aug::verify_hashed_prepared(&ppk, sig, &h));
+    assert!(ppk.verify_hashed(&Signature::<G1, Aug>::from_point(sig), &h));
+    let (_, pk_other) = G1XmdSha256Aug::keygen("another key");
+    let h = HashedMessage::new_aug(&pk_other, msg);
+    assert!(!G1XmdSha256Aug::verify_hashed_prepared(&ppk, sig, &h));
+
+    // a signature outside the subgroup is rejected before the pairing
+    let (_, pk) = G1XmdSha256Basic::keygen("this is the key");
+    let ppk = PreparedPublicKey::<G1, G1XmdSha256Basic>::new(pk).unwrap();
+    assert_eq!(
+        G1XmdSha256Basic::try_verify_prepared(&ppk, g1_non_subgroup_point(), msg),
+        Err(BlsError::NotInSubgroup)
+    );
+}
+
+fn test_aggregator_suite<C: PopCiphersuite>() {
+    let msg = "this is the message";
+    let keys: Vec<_> = (0..4)
+        .map(|i| C::keygen(format!("key number {}", i)))
+        .collect();
+    let sigs: Vec<_> = keys.iter().map(|k| C::sign(k.0, msg)).collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+
+    // adding one at a time matches aggregating the slice
+    let mut sig_agg = Aggregator::new();
+    let mut pk_agg = Aggregator::new();
+    assert!(sig_agg.is_empty());
+    for (sig, pk) in sigs.iter().zip(&pks).take(3) {
+        sig_agg.add(sig);
+        pk_agg.add(pk);
+    }
+    assert_eq!(sig_agg.len(), 3);
+    assert_eq!(
+        sig_agg.as_point(),
+        &<C::Group as BLSSigCore<C::Expand>>::aggregate(&sigs[..3])
+    );
+    assert_eq!(pk_agg, Aggregator::from_points(&pks[..3]));
+    assert!(C::multisig_verify_aggregated(
+        &pk_agg,
+        *sig_agg.as_point(),
+        msg
+    ));
+    assert!(C::multisig_verify(&pks[..3], *sig_agg.as_point(), msg));
+    assert!(!C::multisig_verify_aggregated(
+        &pk_agg,
+        *sig_agg.as_point(),
+        "another message"
+    ));
+
+    // removing a member
+    sig_agg.remove(&sigs[1]).unwrap();
+    pk_agg.remove(&pks[1]).unwrap();
+    assert_eq!(sig_agg.len(), 2);
+    assert!(C::multisig_verify(
+        &[pks[0], pks[2]],
+        *sig_agg.as_point(),
+        msg
+    ));
+    assert!(C::multisig_verify_aggregated(
+        &pk_agg,
+        *sig_agg.as_point(),
+        msg
+    ));
+
+    // merging with a second aggregator
+    let other_sigs = Aggregator::from_points(&[sigs[1], sigs[3]]);
+    let other_pks = Aggregator::from_points(&[pks[1], pks[3]]);
+    sig_agg.merge(&other_sigs);
+    pk_agg.merge(&other_pks);
+    assert_eq!(sig_agg.len(), 4);
+    assert_eq!(pk_agg, Aggregator::from_points(&pks));
+    assert!(C::multisig_verify_aggregated(
+        &pk_agg,
+        *sig_agg.as_point(),
+        msg
+    ));
+
+    // persisting and resuming
+    let resumed = Aggregator::<C::Group>::from_bytes(&sig_agg.to_bytes()).unwrap();
+    assert_eq!(resumed, sig_agg);
+    let resumed = Aggregator::<PkT<C>>::from_bytes(&pk_agg.to_bytes()).unwrap();
+    assert_eq!(resumed, pk_agg);
+    assert_eq!(
+        Aggregator::<C::Group>::from_bytes(&pk_agg.to_bytes()),
+        Err(BlsError::InvalidEncoding)
+    );
+
+    // empty aggregators
+    let mut empty = Aggregator::<PkT<C>>::default();
+    assert_eq!(empty.remove(&pks[0]), Err(BlsError::EmptyInput));
+    assert_eq!(
+        C::try_multisig_verify_aggregated(&empty, *sig_agg.as_point(), msg),
+        Err(BlsError::EmptyInput)
+    );
+    let bytes = empty.to_bytes();
+    assert_eq!(Aggregator::<PkT<C>>::from_bytes(&bytes), Ok(empty));
+    // a zero count with a nonzero sum is inconsistent
+    let mut bad = pk_agg.to_bytes();
+    for b in &mut bad[..8] {
+        *b = 0;
+    }
+    assert_eq!(
+        Aggregator::<PkT<C>>::from_bytes(&bad),
+        Err(BlsError::InvalidEncoding)
+    );
+    // a key and its negation cancel
+    let mut neg = pks[0];
+    neg.negate();
+    let cancel = Aggregator::from_points(&[pks[0], neg]);
+    assert_eq!(
+        C::try_multisig_verify_aggregated(&cancel, *sig_agg.as_point(), msg),
+        Err(BlsError::IdentityPoint)
+    );
+}
+
+#[test]
+fn test_aggregator() {
+    test_aggregator_suite::<G1XmdSha256Pop>();
+    test_aggregator_suite::<G2XmdSha256Pop>();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_aggregator() {
+    use bincode;
+    use serde_json;
+
+    let (_, pk1) = G1XmdSha256Pop::keygen("key number 0");
+    let (_, pk2) = G1XmdSha256Pop::keygen("key number 1");
+    let agg = Aggregator::from_points(&[pk1, pk2]);
+    let json = serde_json::to_string(&agg).unwrap();
+    assert_eq!(json.len(), 2 + 2 * (8 + 96));
+    assert_eq!(serde_json::from_str::<Aggregator<G2>>(&json).unwrap(), agg);
+    let bin = bincode::serialize(&agg).unwrap();
+    assert_eq!(bincode::deserialize::<Aggregator<G2>>(&bin).unwrap(), agg);
+    assert!(serde_json::from_str::<Aggregator<G1>>(&json).is_err());
+}
+
+fn test_weighted_suite<G>()
+where
+    G: BLSSignaturePop<ExpandMsgXmd<Sha256>> + CurveProjective<Scalar = Fr>,
+    Pop: Scheme<G>,
+    <Pop as Scheme<G>>::Suite: PopCiphersuite,
+{
+    let msg = "this is the message";
+    let keys: Vec<_> = (0..5)
+        .map(|i| <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let weights = [3u64, 0, 1 << 40, 7, u64::MAX];
+    let sigs: Vec<_> = keys
+        .iter()
+        .map(|k| <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::sign(k.0, msg))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+
+    // multi-scalar multiplication agrees with one scalar multiplication per point
+    let agg = weighted_aggregate(&sigs, &weights).unwrap();
+    let mut expect = G::zero();
+    for (sig, w) in sigs.iter().zip(&weights) {
+        let mut tmp = *sig;
+        tmp.mul_assign(FrRepr::from(*w));
+        expect.add_assign(&tmp);
+    }
+    assert_eq!(agg, expect);
+    assert_eq!(weighted_aggregate(&sigs[..1], &[1]).unwrap(), sigs[0]);
+    assert_eq!(
+        weighted_aggregate(&sigs[..2], &weights),
+        Err(BlsError::LengthMismatch)
+    );
+    assert_eq!(weighted_aggregate::<G>(&[], &[]), Err(BlsError::EmptyInput));
+
+    let signers: Vec<_> = pks.iter().cloned().zip(weights.iter().cloned()).collect();
+    let total = 3 + (1 << 40) + 7 + u128::from(u64::MAX);
+    assert!(total > u128::from(u64::MAX));
+    assert!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::weighted_multisig_verify(
+            &signers,
+            agg,
+            msg,
+            u64::MAX
+        )
+    );
+    assert!(
+        !<G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::weighted_multisig_verify(
+            &signers,
+            agg,
+            "another message",
+            0
+        )
+    );
+    // wrong weights
+    let mut wrong = signers.clone();
+    wrong[0].1 = 4;
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_weighted_multisig_verify(
+            &wrong, agg, msg, 0
+        ),
+        Err(BlsError::PairingCheckFailed)
+    );
+    // below the threshold
+    let few = &signers[..2];
+    let few_agg = weighted_aggregate(&sigs[..2], &weights[..2]).unwrap();
+    assert!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::weighted_multisig_verify(
+            few, few_agg, msg, 3
+        )
+    );
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_weighted_multisig_verify(
+            few, few_agg, msg, 4
+        ),
+        Err(BlsError::InsufficientWeight)
+    );
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_weighted_multisig_verify(
+            &[],
+            few_agg,
+            msg,
+            0
+        ),
+        Err(BlsError::EmptyInput)
+    );
+    // all weights zero
+    assert_eq!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::try_weighted_multisig_verify(
+            &signers[1..2],
+            G::zero(),
+            msg,
+            0
+        ),
+        Err(BlsError::IdentityPoint)
+    );
+    // unit weights reduce to the plain multisignature
+    let ones = vec![1u64; pks.len()];
+    let unit: Vec<_> = pks.iter().cloned().zip(ones.iter().cloned()).collect();
+    let multisig = weighted_aggregate(&sigs, &ones).unwrap();
+    assert!(<G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::multisig_verify(&pks, multisig, msg));
+    assert!(
+        <G as BLSSignaturePop<ExpandMsgXmd<Sha256>>>::weighted_multisig_verify(
+            &unit, multisig, msg, 5
+        )
+    );
+
+    // typed wrappers
+    let sks: Vec<_> = (0..3)
+        .map(|i| SecretKey::<G, Pop>::new(format!("key number {}", i)))
+        .collect();
+    let typed_sigs: Vec<_> = sks.iter().map(|sk| sk.sign(msg)).collect();
+    let typed_agg = Signature::weighted_aggregate(&typed_sigs, &weights[..3]).unwrap();
+    assert_eq!(
+        typed_agg.as_point(),
+        &weighted_aggregate(&sigs[..3], &weights[..3]).unwrap()
+    );
+    let typed_signers: Vec<_> = sks
+        .iter()
+        .map(|sk| sk.public_key())
+        .zip(weights.iter().cloned())
+        .collect();
+    assert!(typed_agg.weighted_multisig_verify(&typed_signers, msg, 1 << 40));
+    assert_eq!(
+        typed_agg.try_weighted_multisig_verify(&typed_signers, msg, (1 << 40) + 4),
+        Err(BlsError::InsufficientWeight)
+    );
+}
+
+#[test]
+fn test_weighted_multisig() {
+    test_weighted_suite::<G1>();
+    test_weighted_suite::<G2>();
+
+    // the ciphersuite interface
+    let msg = "this is the message";
+    let (x1, pk1) = G2XofShake256Pop::keygen("key number 0");
+    let (x2, pk2) = G2XofShake256Pop::keygen("key number 1");
+    let agg = weighted_aggregate(
+        &[
+            G2XofShake256Pop::sign(x1, msg),
+            G2XofShake256Pop::sign(x2, msg),
+        ],
+        &[10, 20],
+    )
+    .unwrap();
+    assert!(G2XofShake256Pop::weighted_multisig_verify(
+        &[(pk1, 10), (pk2, 20)],
+        agg,
+        msg,
+        30
+    ));
+    assert_eq!(
+        G2XofShake256Pop::try_weighted_multisig_verify(&[(pk1, 10), (pk2, 20)], agg, msg, 31),
+        Err(BlsError::InsufficientWeight)
+    );
+    assert_eq!(
+        G2XofShake256Pop::try_weighted_multisig_verify(&[(pk1, 20), (pk2, 10)], agg, msg, 30),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        G2XofShake256Pop::try_weighted_multisig_verify(&[(G1::zero(), 1)], agg, msg, 0),
+        Err(BlsError::IdentityPoint)
+    );
+}
+
+fn test_bitfield_suite<C: PopCiphersuite>() {
+    let msg = "this is the message";
+    let n = 11;
+    let keys: Vec<_> = (0..n)
+        .map(|i| C::keygen(format!("key number {}", i)))
+        .collect();
+    let members: Vec<_> = keys.iter().map(|k| (k.1, C::pop_prove(k.0))).collect();
+    let committee = Committee::<C>::new(&members).unwrap();
+    assert_eq!(committee.len(), n);
+    let sigs: Vec<_> = keys.iter().map(|k| C::sign(k.0, msg)).collect();
+
+    // a bad proof of possession keeps a key out of the committee
+    let mut bad_members = members.clone();
+    bad_members[3].1 = members[4].1;
+    assert_eq!(
+        Committee::<C>::new(&bad_members).unwrap_err(),
+        BlsError::PairingCheckFailed
+    );
+
+    // two partial certificates with disjoint signers
+    let mut cert1 = BitfieldAggregate::<C>::new(n).unwrap();
+    let mut cert2 = BitfieldAggregate::<C>::new(n).unwrap();
+    for i in &[0, 3, 9] {
+        cert1.add(*i, &sigs[*i]).unwrap();
+    }
+    for i in &[1, 10] {
+        cert2.add(*i, &sigs[*i]).unwrap();
+    }
+    assert_eq!(cert1.add(3, &sigs[3]), Err(BlsError::DuplicateSigner));
+    assert_eq!(cert1.add(n, &sigs[0]), Err(BlsError::InvalidSignerIndex));
+    assert!(cert1.verify(&committee, msg));
+    assert!(cert2.verify(&committee, msg));
+    assert!(!cert1.verify(&committee, "another message"));
+
+    let mut merged = cert1.clone();
+    merged.merge(&cert2).unwrap();
+    assert_eq!(merged.signers(), vec![0, 1, 3, 9, 10]);
+    assert_eq!(merged.count(), 5);
+    assert!(merged.contains(10) && !merged.contains(2) && !merged.contains(n));
+    assert!(merged.verify(&committee, msg));
+    assert_eq!(merged.merge(&cert2), Err(BlsError::DuplicateSigner));
+    assert_eq!(
+        merged.merge(&BitfieldAggregate::new(n + 1).unwrap()),
+        Err(BlsError::LengthMismatch)
+    );
+    // the size must fit in the 4-byte header of the encoding
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(
+        BitfieldAggregate::<C>::new(u32::MAX as usize + 1),
+        Err(BlsError::CommitteeTooLarge)
+    );
+
+    // the bitmap must match the signature
+    let mut wrong = BitfieldAggregate::<C>::new(n).unwrap();
+    wrong.add(2, &sigs[0]).unwrap();
+    assert_eq!(
+        wrong.try_verify(&committee, msg),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        BitfieldAggregate::<C>::new(n)
+            .unwrap()
+            .try_verify(&committee, msg),
+        Err(BlsError::EmptyInput)
+    );
+    let small = Committee::<C>::new(&members[..n - 1]).unwrap();
+    assert_eq!(
+        merged.try_verify(&small, msg),
+        Err(BlsError::LengthMismatch)
+    );
+
+    // serialization
+    let bytes = merged.to_bytes();
+    assert_eq!(bytes[bytes.len() - 2..], [0x0b, 0x06][..]);
+    let decoded = BitfieldAggregate::<C>::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, merged);
+    assert!(decoded.verify(&committee, msg));
+    assert_eq!(
+        BitfieldAggregate::<C>::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(BlsError::InvalidEncoding)
+    );
+    // a bit past the end of the committee
+    let mut padded = bytes.clone();
+    *padded.last_mut().unwrap() |= 0x80;
+    assert_eq!(
+        BitfieldAggregate::<C>::from_bytes(&padded),
+        Err(BlsError::InvalidEncoding)
+    );
+    let mut grown = bytes.clone();
+    grown[3] = 17;
+    assert_eq!(
+        BitfieldAggregate::<C>::from_bytes(&grown),
+        Err(BlsError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_bitfield_aggregate() {
+    test_bitfield_suite::<G1XmdSha256Pop>();
+    test_bitfield_suite::<G2XmdSha256Pop>();
+    test_bitfield_suite::<G1XofShake256Pop>();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_bitfield() {
+    use bincode;
+    use serde_json;
+
+    let msg = "this is the message";
+    let (x, _) = G1XmdSha256Pop::keygen("key number 0");
+    let mut cert = BitfieldAggregate::<G1XmdSha256Pop>::new(3).unwrap();
+    cert.add(1, &G1XmdSha256Pop::sign(x, msg)).unwrap();
+    let json = serde_json::to_string(&cert).unwrap();
+    assert_eq!(
+        serde_json::from_str::<BitfieldAggregate<G1XmdSha256Pop>>(&json).unwrap(),
+        cert
+    );
+    let bin = bincode::serialize(&cert).unwrap();
+    assert_eq!(
+        bincode::deserialize::<BitfieldAggregate<G1XmdSha256Pop>>(&bin).unwrap(),
+        cert
+    );
+    assert!(serde_json::from_str::<BitfieldAggregate<G2XmdSha256Pop>>(&json).is_err());
+}
+
+fn test_committee_suite<C: PopCiphersuite>() {
+    let msg = "this is the message";
+    let n = 9;
+    let keys: Vec<_> = (0..n + 1)
+        .map(|i| C::keygen(format!("key number {}", i)))
+        .collect();
+    let members: Vec<_> = keys[..n].iter().map(|k| (k.1, C::pop_prove(k.0))).collect();
+    let mut committee = Committee::<C>::new(&members).unwrap();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let sigs: Vec<_> = keys.iter().map(|k| C::sign(k.0, msg)).collect();
+    assert_eq!(*committee.aggregate_public_key(), _agg_help(&pks[..n]));
+
+    // the whole committee
+    let all = _agg_help(&sigs[..n]);
+    assert!(committee.multisig_verify(all, msg));
+    assert!(C::multisig_verify(&pks[..n], all, msg));
+    assert!(!committee.multisig_verify(all, "another message"));
+
+    // few signers (sum of signers) and most signers (full sum minus non-signers)
+    for signers in &[vec![4], vec![0, 2, 7], vec![0, 1, 2, 3, 5, 6, 7, 8]] {
+        let mut cert = BitfieldAggregate::<C>::new(n).unwrap();
+        for &i in signers {
+            cert.add(i, &sigs[i]).unwrap();
+        }
+        let apk = _agg_help(&signers.iter().map(|&i| pks[i]).collect::<Vec<_>>());
+        assert_eq!(committee.signer_apk(cert.as_bits()), apk);
+        assert!(cert.verify(&committee, msg));
+    }
+
+    // join and leave keep the cached sum up to date
+    assert_eq!(
+        committee.join(pks[n], C::pop_prove(keys[0].0)),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(committee.join(pks[n], C::pop_prove(keys[n].0)), Ok(n));
+    assert_eq!(*committee.aggregate_public_key(), _agg_help(&pks[..]));
+    assert!(committee.multisig_verify(_agg_help(&sigs[..]), msg));
+    assert_eq!(committee.leave(0), Ok(pks[0]));
+    assert_eq!(committee.leave(n), Err(BlsError::InvalidSignerIndex));
+    assert_eq!(committee.members(), &pks[1..]);
+    assert_eq!(*committee.aggregate_public_key(), _agg_help(&pks[1..]));
+    assert!(committee.multisig_verify(_agg_help(&sigs[1..]), msg));
+    let mut cert = BitfieldAggregate::<C>::new(n).unwrap();
+    for i in 0..n - 1 {
+        cert.add(i, &sigs[i + 1]).unwrap();
+    }
+    assert!(cert.verify(&committee, msg));
+
+    // an empty committee
+    let empty = Committee::<C>::from_prevalidated(Vec::new());
+    assert_eq!(
+        empty.try_multisig_verify(all, msg),
+        Err(BlsError::EmptyInput)
+    );
+}
+
+#[test]
+fn test_committee() {
+    test_committee_suite::<G1XmdSha256Pop>();
+    test_committee_suite::<G2XmdSha256Pop>();
+}
+
+fn test_bdn_suite<G>()
+where
+    G: BLSSignatureBdn<ExpandMsgXmd<Sha256>> + CurveProjective<Scalar = Fr>,
+{
+    let msg = "this is the message";
+    let keys: Vec<_> = (0..4)
+        .map(|i| <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen(format!("key number {}", i)))
+        .collect();
+    let pks: Vec<_> = keys.iter().map(|k| k.1).collect();
+    let sigs: Vec<_> = keys
+        .iter()
+        .map(|k| <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::sign(k.0, msg))
+        .collect();
+    for (pk, sig) in pks.iter().zip(&sigs) {
+        assert!(<G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::verify(
+            *pk, *sig, msg
+        ));
+    }
+
+    // the aggregates weight each key and signature by its coefficient
+    let coefs = _bdn_coefficients::<ExpandMsgXmd<Sha256>, _>(
+        &pks,
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::CSUITE_COEF,
+    );
+    assert_eq!(coefs.len(), pks.len());
+    assert!(coefs.iter().all(|t| !t.is_zero()));
+    assert_ne!(coefs[0], coefs[1]);
+    let mut apk = pks[0];
+    apk.mul_assign(coefs[0]);
+    let mut agg = sigs[0];
+    agg.mul_assign(coefs[0]);
+    for i in 1..pks.len() {
+        let mut tmp = pks[i];
+        tmp.mul_assign(coefs[i]);
+        apk.add_assign(&tmp);
+        let mut tmp = sigs[i];
+        tmp.mul_assign(coefs[i]);
+        agg.add_assign(&tmp);
+    }
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::bdn_aggregate_pks(&pks),
+        Ok(apk)
+    );
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::bdn_aggregate(&pks, &sigs),
+        Ok(agg)
+    );
+    assert!(<G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::multisig_verify(&pks, agg, msg));
+    assert!(
+        !<G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::multisig_verify(
+            &pks,
+            agg,
+            "another message"
+        )
+    );
+    // the plain sum is not a BDN multisignature
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::try_multisig_verify(
+            &pks,
+            _agg_help(&sigs),
+            msg
+        ),
+        Err(BlsError::PairingCheckFailed)
+    );
+
+    // the coefficients depend on the order of the keys
+    let rev_pks: Vec<_> = pks.iter().rev().cloned().collect();
+    let rev_sigs: Vec<_> = sigs.iter().rev().cloned().collect();
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::try_multisig_verify(&rev_pks, agg, msg),
+        Err(BlsError::PairingCheckFailed)
+    );
+    let rev_agg =
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::bdn_aggregate(&rev_pks, &rev_sigs).unwrap();
+    assert!(<G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::multisig_verify(&rev_pks, rev_agg, msg));
+
+    // a rogue key pk_r = a * g - pk_0 lets its owner forge a plain multisignature by both
+    // keys, but not a BDN one
+    let a = Fr::from_str("12345").unwrap();
+    let mut rogue = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::PKType::one();
+    rogue.mul_assign(a);
+    rogue.sub_assign(&pks[0]);
+    let forged = <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::sign(a, msg);
+    let mut plain_apk = pks[0];
+    plain_apk.add_assign(&rogue);
+    assert!(<G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::verify(
+        plain_apk, forged, msg
+    ));
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::try_multisig_verify(
+            &[pks[0], rogue],
+            forged,
+            msg
+        ),
+        Err(BlsError::PairingCheckFailed)
+    );
+
+    // invalid inputs
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::bdn_aggregate(&pks[..2], &sigs),
+        Err(BlsError::LengthMismatch)
+    );
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::bdn_aggregate(&[], &[]),
+        Err(BlsError::EmptyInput)
+    );
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::try_multisig_verify(&[], agg, msg),
+        Err(BlsError::EmptyInput)
+    );
+    let zero = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::PKType::zero();
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::try_multisig_verify(
+            &[pks[0], zero],
+            agg,
+            msg
+        ),
+        Err(BlsError::IdentityPoint)
+    );
+    assert_eq!(
+        <G as BLSSignatureBdn<ExpandMsgXmd<Sha256>>>::try_multisig_verify_prevalidated(
+            &[zero],
+            agg,
+            msg
+        ),
+        Err(BlsError::IdentityPoint)
+    );
+}
+
+#[test]
+fn test_bdn_multisig() {
+    test_bdn_suite::<G1>();
+    test_bdn_suite::<G2>();
+}
+
+fn test_threshold_suite<G>()
+where
+    G: BLSSignatureThreshold<ExpandMsgXmd<Sha256>> + PointEncoding,
+{
+    let msg = "this is the message";
+    let (secret, pk) = <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::keygen("the shared key");
+    let shares = split_secret(&secret, 3, 5, &mut thread_rng()).unwrap();
+    assert_eq!(
+        shares.iter().map(|s| s.index()).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5]
+    );
+    let pk_shares: Vec<_> = shares.iter().map(G::public_key_share).collect();
+    let partials: Vec<_> = shares.iter().map(|s| G::partial_sign(s, msg)).collect();
+    for (pk_share, partial) in pk_shares.iter().zip(&partials) {
+        assert!(G::partial_verify(pk_share, partial, msg));
+        assert!(!G::partial_verify(pk_share, partial, "another message"));
+    }
+    assert_eq!(
+        G::try_partial_verify(&pk_shares[0], &partials[1], msg),
+        Err(BlsError::InvalidSignerIndex)
+    );
+
+    // any three shares give the signature of the secret itself
+    let expect = <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(secret, msg);
+    for subset in &[
+        vec![0, 1, 2],
+        vec![4, 2, 0],
+        vec![1, 3, 4],
+        vec![0, 1, 2, 3, 4],
+    ] {
+        let some: Vec<_> = subset.iter().map(|&i| partials[i]).collect();
+        let sig = G::combine(&some).unwrap();
+        assert_eq!(sig, expect);
+        assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+            pk, sig, msg
+        ));
+        let some_pks: Vec<_> = subset.iter().map(|&i| pk_shares[i]).collect();
+        assert_eq!(G::combine_public_keys(&some_pks), Ok(pk));
+    }
+    // two shares are not enough
+    let sig = G::combine(&partials[..2]).unwrap();
+    assert!(!<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        pk, sig, msg
+    ));
+    // a bad partial spoils the result
+    let mut bad = partials[..3].to_vec();
+    bad[1] = PartialSignature::new(2, *partials[3].as_point()).unwrap();
+    assert!(!G::partial_verify(&pk_shares[1], &bad[1], msg));
+    assert_ne!(G::combine(&bad).unwrap(), expect);
+
+    // invalid inputs
+    assert_eq!(G::combine(&[]), Err(BlsError::EmptyInput));
+    assert_eq!(
+        G::combine(&[partials[0], partials[1], partials[0]]),
+        Err(BlsError::DuplicateSigner)
+    );
+
+    // encoding
+    let bytes = partials[4].to_bytes();
+    assert_eq!(bytes.len(), 4 + G::zero().to_compressed().as_ref().len());
+    assert_eq!(bytes[..4], [0, 0, 0, 5]);
+    assert_eq!(PartialSignature::<G>::from_bytes(&bytes), Ok(partials[4]));
+    assert_eq!(
+        PartialSignature::<G>::from_bytes(&bytes[1..]),
+        Err(BlsError::InvalidEncoding)
+    );
+    let mut zero_index = bytes.clone();
+    zero_index[3] = 0;
+    assert_eq!(
+        PartialSignature::<G>::from_bytes(&zero_index),
+        Err(BlsError::InvalidSignerIndex)
+    );
+}
+
+#[test]
+fn test_threshold() {
+    test_threshold_suite::<G1>();
+    test_threshold_suite::<G2>();
+
+    // interpolating the shares themselves recovers the secret
+    let secret = xprime_from_sk("the shared key");
+    let shares = split_secret(&secret, 4, 7, &mut thread_rng()).unwrap();
+    let some = [&shares[6], &shares[2], &shares[0], &shares[5]];
+    let lambdas = _lagrange_at_zero(&some.iter().map(|s| s.index()).collect::<Vec<_>>()).unwrap();
+    let mut recovered = Fr::zero();
+    for (share, lambda) in some.iter().zip(&lambdas) {
+        let mut tmp = *share.value();
+        tmp.mul_assign(lambda);
+        recovered.add_assign(&tmp);
+    }
+    assert_eq!(recovered, secret);
+
+    // a threshold of one hands every party the secret
+    let shares = split_secret(&secret, 1, 3, &mut thread_rng()).unwrap();
+    assert!(shares.iter().all(|s| *s.value() == secret));
+
+    assert_eq!(
+        split_secret(&secret, 0, 3, &mut thread_rng()).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        split_secret(&secret, 4, 3, &mut thread_rng()).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        SecretShare::new(0, secret).unwrap_err(),
+        BlsError::InvalidSignerIndex
+    );
+    assert_eq!(
+        format!("{:?}", SecretShare::new(2, secret).unwrap()),
+        "SecretShare(2, <redacted>)"
+    );
+    assert_eq!(
+        _lagrange_at_zero(&[1, 0]).unwrap_err(),
+        BlsError::InvalidSignerIndex
+    );
+}
+
+// how a simulated DKG participant deviates from the protocol
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DkgBehavior {
+    Honest,
+    // sends participant j a wrong share, then answers j's complaint honestly
+    BadShare(u32),
+    // sends participant j a wrong share, and answers j's complaint with another
+    BadAnswer(u32),
+    // sends participant j nothing, and ignores j's complaint
+    Withhold(u32),
+    // broadcasts a commitment to a polynomial of the wrong degree
+    BadCommitment,
+    // complains about honest dealer d
+    FalseComplaint(u32),
+}
+
+// run a DKG among behaviors.len() participants, delivering every message through its encoding
+fn run_dkg<P: PointEncoding + CurveProjective<Scalar = Fr>>(
+    threshold: usize,
+    behaviors: &[DkgBehavior],
+) -> Vec<DkgOutput<P>> {
+    use self::DkgBehavior::*;
+    let n = behaviors.len();
+    let mut rng = thread_rng();
+    let mut parties: Vec<DkgParticipant<P>> = (1..=n as u32)
+        .map(|i| DkgParticipant::new(i, threshold, n).unwrap())
+        .collect();
+
+    // round 1: commitments and private shares
+    let mut commitments = Vec::new();
+    let mut shares = Vec::new();
+    for (party, behavior) in parties.iter_mut().zip(behaviors) {
+        let (mut commitment, dealt) = party.deal(&mut rng);
+        if *behavior == BadCommitment {
+            let mut coeffs = commitment.coefficients().to_vec();
+            coeffs.push(P::one());
+            commitment = DkgCommitment::new(party.index(), coeffs).unwrap();
+        }
+        commitments.push(commitment.to_bytes());
+        for share in dealt {
+            let mut bytes = share.to_bytes();
+            match *behavior {
+                Withhold(j) if j == share.recipient() => continue,
+                BadShare(j) | BadAnswer(j) if j == share.recipient() => bytes[39] ^= 1,
+                _ => (),
+            }
+            shares.push(bytes);
+        }
+    }
+    for party in parties.iter_mut() {
+        for bytes in &commitments {
+            let commitment = DkgCommitment::from_bytes(bytes).unwrap();
+            let bad = behaviors[commitment.dealer() as usize - 1] == BadCommitment;
+            assert_eq!(
+                party.receive_commitment(commitment).is_err(),
+                bad,
+                "commitment rejected iff malformed"
+            );
+        }
+    }
+    for bytes in &shares {
+        let share = DkgShare::from_bytes(bytes).unwrap();
+        let party = &mut parties[share.recipient() as usize - 1];
+        let corrupt = match behaviors[share.dealer() as usize - 1] {
+            BadShare(j) | BadAnswer(j) => j == share.recipient(),
+            BadCommitment => true,
+            _ => false,
+        };
+        assert_eq!(party.receive_share(share).is_err(), corrupt);
+    }
+
+    // round 2: complaints
+    let mut complaints = Vec::new();
+    for (party, behavior) in parties.iter().zip(behaviors) {
+        let mut mine = party.complaints();
+        if let FalseComplaint(d) = *behavior {
+            mine.push(DkgComplaint::new(d, party.index()).unwrap());
+        }
+        complaints.extend(mine.iter().map(DkgComplaint::to_bytes));
+    }
+    for party in parties.iter_mut() {
+        for bytes in &complaints {
+            party
+                .receive_complaint(DkgComplaint::from_bytes(bytes).unwrap())
+                .unwrap();
+        }
+    }
+
+    // round 3: answers
+    let mut answers = Vec::new();
+    for (party, behavior) in parties.iter().zip(behaviors) {
+        for share in party.responses() {
+            let mut bytes = share.to_bytes();
+            match *behavior {
+                Withhold(_) => continue,
+                BadAnswer(_) => bytes[39] ^= 2,
+                _ => (),
+            }
+            answers.push(bytes);
+        }
+    }
+    for party in parties.iter_mut() {
+        for bytes in &answers {
+            let share = DkgShare::from_bytes(bytes).unwrap();
+            let bad = matches!(behaviors[share.dealer() as usize - 1], BadAnswer(_));
+            assert_eq!(party.receive_response(share).is_err(), bad);
+        }
+    }
+
+    parties.into_iter().map(|p| p.finish().unwrap()).collect()
+}
+
+fn test_dkg_suite<G: BLSSignatureThreshold<ExpandMsgXmd<Sha256>>>() {
+    use self::DkgBehavior::*;
+    let msg = "signed by the committee";
+    let behaviors = [
+        Honest,
+        BadShare(3),
+        BadAnswer(1),
+        Honest,
+        Withhold(2),
+        BadCommitment,
+        FalseComplaint(4),
+    ];
+    let outputs = run_dkg::<G::PKType>(3, &behaviors);
+
+    // everyone agrees on the qualified dealers and the joint commitments
+    assert_eq!(outputs[0].qualified(), &[1, 2, 4, 7]);
+    for out in &outputs {
+        assert_eq!(out.qualified(), outputs[0].qualified());
+        assert_eq!(out.commitments(), outputs[0].commitments());
+    }
+    let pk = outputs[0].public_key();
+    assert!(!pk.is_zero());
+
+    // each share matches its public key share, and any three of them sign for the group
+    let pk_shares: Vec<_> = (1..=7u32)
+        .map(|i| outputs[0].public_key_share(i).unwrap())
+        .collect();
+    for (out, pk_share) in outputs.iter().zip(&pk_shares) {
+        assert_eq!(&G::public_key_share(out.secret_share()), pk_share);
+    }
+    let partials: Vec<_> = outputs
+        .iter()
+        .map(|o| G::partial_sign(o.secret_share(), msg))
+        .collect();
+    for subset in &[vec![0, 1, 2], vec![6, 4, 2], vec![1, 3, 5, 6]] {
+        let some: Vec<_> = subset.iter().map(|&i| partials[i]).collect();
+        let sig = G::combine(&some).unwrap();
+        assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+            pk, sig, msg
+        ));
+        let some_pks: Vec<_> = subset.iter().map(|&i| pk_shares[i]).collect();
+        assert_eq!(G::combine_public_keys(&some_pks), Ok(pk));
+    }
+    let sig = G::combine(&partials[..2]).unwrap();
+    assert!(!<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        pk, sig, msg
+    ));
+
+    // with no misbehavior every dealer qualifies
+    let outputs = run_dkg::<G::PKType>(2, &[Honest; 4]);
+    assert_eq!(outputs[3].qualified(), &[1, 2, 3, 4]);
+    let sig = G::combine(&[
+        G::partial_sign(outputs[3].secret_share(), msg),
+        G::partial_sign(outputs[0].secret_share(), msg),
+    ])
+    .unwrap();
+    assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        outputs[0].public_key(),
+        sig,
+        msg
+    ));
+}
+
+#[test]
+fn test_dkg() {
+    test_dkg_suite::<G1>();
+    test_dkg_suite::<G2>();
+
+    // a threshold of one among one participant
+    let outputs = run_dkg::<G2>(1, &[DkgBehavior::Honest]);
+    let mut pk = G2::one();
+    pk.mul_assign(*outputs[0].secret_share().value());
+    assert_eq!(outputs[0].public_key(), pk);
+
+    // invalid parameters and messages
+    assert_eq!(
+        DkgParticipant::<G2>::new(1, 0, 3).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        DkgParticipant::<G2>::new(1, 4, 3).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        DkgParticipant::<G2>::new(4, 2, 3).unwrap_err(),
+        BlsError::InvalidSignerIndex
+    );
+    let mut rng = thread_rng();
+    let mut alice = DkgParticipant::<G1>::new(1, 2, 3).unwrap();
+    let mut bob = DkgParticipant::<G1>::new(2, 2, 3).unwrap();
+    let (commitment, shares) = bob.deal(&mut rng);
+    assert_eq!(shares.len(), 3);
+    assert_eq!(commitment.coefficients().len(), 2);
+    // a share before its dealer's commitment cannot be checked
+    assert_eq!(
+        alice.receive_share(shares[0].clone()),
+        Err(BlsError::InvalidShare)
+    );
+    alice.receive_commitment(commitment.clone()).unwrap();
+    assert_eq!(
+        alice.receive_commitment(commitment.clone()),
+        Err(BlsError::DuplicateSigner)
+    );
+    assert_eq!(
+        alice.receive_share(shares[1].clone()),
+        Err(BlsError::InvalidSignerIndex)
+    );
+    assert_eq!(commitment.verify_share(&shares[1]), Ok(()));
+    let other = DkgShare::new(2, 1, *shares[1].value()).unwrap();
+    assert_eq!(commitment.verify_share(&other), Err(BlsError::InvalidShare));
+    alice.receive_share(shares[0].clone()).unwrap();
+    assert_eq!(
+        alice.receive_share(shares[0].clone()),
+        Err(BlsError::DuplicateSigner)
+    );
+    assert_eq!(alice.complaints(), vec![]);
+    assert_eq!(
+        alice.receive_response(shares[0].clone()),
+        Err(BlsError::InvalidSignerIndex)
+    );
+    assert_eq!(
+        alice.receive_complaint(DkgComplaint::new(4, 1).unwrap()),
+        Err(BlsError::InvalidSignerIndex)
+    );
+    alice
+        .receive_complaint(DkgComplaint::new(2, 3).unwrap())
+        .unwrap();
+    assert_eq!(
+        alice.receive_complaint(DkgComplaint::new(2, 3).unwrap()),
+        Err(BlsError::DuplicateSigner)
+    );
+    // an unanswered complaint disqualifies the only committed dealer
+    assert_eq!(alice.finish().unwrap_err(), BlsError::EmptyInput);
+    // bob answers complaints against himself from the shares he dealt
+    bob.receive_complaint(DkgComplaint::new(2, 3).unwrap())
+        .unwrap();
+    assert_eq!(bob.responses(), vec![shares[2].clone()]);
+
+    // encodings
+    let bytes = commitment.to_bytes();
+    assert_eq!(bytes.len(), 4 + 2 * 48);
+    assert_eq!(DkgCommitment::<G1>::from_bytes(&bytes), Ok(commitment));
+    assert_eq!(
+        DkgCommitment::<G1>::from_bytes(&bytes[..4]),
+        Err(BlsError::InvalidCommitment)
+    );
+    assert_eq!(
+        DkgCommitment::<G1>::from_bytes(&bytes[1..]),
+        Err(BlsError::InvalidEncoding)
+    );
+    assert_eq!(
+        DkgShare::from_bytes(&shares[2].to_bytes()),
+        Ok(shares[2].clone())
+    );
+    let mut bytes = shares[2].to_bytes();
+    bytes[8] = 0xff;
+    assert_eq!(DkgShare::from_bytes(&bytes), Err(BlsError::InvalidEncoding));
+    let mut bytes = shares[2].to_bytes();
+    bytes[3] = 0;
+    assert_eq!(
+        DkgShare::from_bytes(&bytes),
+        Err(BlsError::InvalidSignerIndex)
+    );
+    let complaint = DkgComplaint::new(2, 3).unwrap();
+    assert_eq!(complaint.to_bytes(), [0, 0, 0, 2, 0, 0, 0, 3]);
+    assert_eq!(
+        DkgComplaint::from_bytes(&complaint.to_bytes()),
+        Ok(complaint)
+    );
+    assert_eq!(
+        DkgComplaint::from_bytes(&[0, 0, 0, 2, 0, 0, 0, 0]),
+        Err(BlsError::InvalidSignerIndex)
+    );
+    assert_eq!(format!("{:?}", shares[2]), "DkgShare(2, 3, <redacted>)");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_dkg() {
+    use serde_json;
+    let mut bob = DkgParticipant::<G2>::new(2, 2, 3).unwrap();
+    let (commitment, _shares) = bob.deal(&mut thread_rng());
+    let json = serde_json::to_string(&commitment).unwrap();
+    assert_eq!(json.len(), 2 + 2 * (4 + 2 * 96));
+    assert_eq!(
+        serde_json::from_str::<DkgCommitment<G2>>(&json).unwrap(),
+        commitment
+    );
+    let bin = bincode::serialize(&commitment).unwrap();
+    assert_eq!(
+        bincode::deserialize::<DkgCommitment<G2>>(&bin).unwrap(),
+        commitment
+    );
+    let complaint = DkgComplaint::new(2, 3).unwrap();
+    let json = serde_json::to_string(&complaint).unwrap();
+    assert_eq!(json, "\"0000000200000003\"");
+    assert_eq!(
+        serde_json::from_str::<DkgComplaint>(&json).unwrap(),
+        complaint
+    );
+    #[cfg(feature = "serde-secret-keys")]
+    {
+        let json = serde_json::to_string(&_shares[0]).unwrap();
+        assert_eq!(serde_json::from_str::<DkgShare>(&json).unwrap(), _shares[0]);
+        assert!(serde_json::from_str::<DkgShare>(&json.replacen("\"", "\"00", 1)).is_err());
+    }
+}
+
+fn test_refresh_suite<G: BLSSignatureThreshold<ExpandMsgXmd<Sha256>>>() {
+    let msg = "signed before and after the refresh";
+    let mut rng = thread_rng();
+    let old = run_dkg::<G::PKType>(3, &[DkgBehavior::Honest; 5]);
+    let pk = old[0].public_key();
+    let old_partials: Vec<_> = old
+        .iter()
+        .map(|o| G::partial_sign(o.secret_share(), msg))
+        .collect();
+    let expect = G::combine(&old_partials[..3]).unwrap();
+
+    // member 2 deals a polynomial that would change the secret, and member 4 sends member
+    // 1 a bad share but answers the complaint
+    let mut members: Vec<_> = old
+        .iter()
+        .map(|o| ShareRefresh::new(o, 5).unwrap())
+        .collect();
+    let mut commitments = Vec::new();
+    let mut shares = Vec::new();
+    for m in members.iter_mut() {
+        let (commitment, dealt) = m.deal(&mut rng);
+        if m.index() == 2 {
+            let mut coeffs = commitment.coefficients().to_vec();
+            coeffs[0] = G::PKType::one();
+            commitments.push(DkgCommitment::new(2, coeffs).unwrap());
+        } else {
+            commitments.push(commitment);
+        }
+        for share in dealt {
+            if m.index() == 4 && share.recipient() == 1 {
+                shares.push(DkgShare::new(4, 1, Fr::one()).unwrap());
+            } else {
+                shares.push(share);
+            }
+        }
+    }
+    for m in members.iter_mut() {
+        for c in &commitments {
+            let res = m.receive_commitment(c.clone());
+            assert_eq!(res.is_err(), c.dealer() == 2);
+        }
+    }
+    for share in shares {
+        let corrupt = share.dealer() == 2 || (share.dealer(), share.recipient()) == (4, 1);
+        let m = &mut members[share.recipient() as usize - 1];
+        assert_eq!(m.receive_share(share).is_err(), corrupt);
+    }
+    let complaints: Vec<_> = members.iter().flat_map(|m| m.complaints()).collect();
+    assert_eq!(complaints, vec![DkgComplaint::new(4, 1).unwrap()]);
+    for m in members.iter_mut() {
+        for c in &complaints {
+            m.receive_complaint(*c).unwrap();
+        }
+    }
+    let answers: Vec<_> = members.iter().flat_map(|m| m.responses()).collect();
+    assert_eq!(answers.len(), 1);
+    for m in members.iter_mut() {
+        for a in &answers {
+            m.receive_response(a.clone()).unwrap();
+        }
+    }
+    let new: Vec<_> = members.into_iter().map(|m| m.finish().unwrap()).collect();
+
+    // the key is unchanged, but the shares are new
+    assert_eq!(new[0].qualified(), &[1, 3, 4, 5]);
+    for (n, o) in new.iter().zip(&old) {
+        assert_eq!(n.public_key(), pk);
+        assert_eq!(n.commitments(), new[0].commitments());
+        assert_ne!(n.secret_share(), o.secret_share());
+        assert_eq!(
+            &G::public_key_share(n.secret_share()),
+            &new[0].public_key_share(n.secret_share().index()).unwrap()
+        );
+    }
+    let partials: Vec<_> = new
+        .iter()
+        .map(|o| G::partial_sign(o.secret_share(), msg))
+        .collect();
+    for subset in &[vec![0, 1, 2], vec![4, 3, 1]] {
+        let some: Vec<_> = subset.iter().map(|&i| partials[i]).collect();
+        let sig = G::combine(&some).unwrap();
+        assert_eq!(sig, expect);
+        assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+            pk, sig, msg
+        ));
+    }
+    // old and new shares do not mix
+    let mixed = [old_partials[0], old_partials[1], partials[2]];
+    assert!(!<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        pk,
+        G::combine(&mixed).unwrap(),
+        msg
+    ));
+}
+
+fn test_reshare_suite<G: BLSSignatureThreshold<ExpandMsgXmd<Sha256>>>() {
+    let msg = "signed by the old and new committees";
+    let mut rng = thread_rng();
+    let old = run_dkg::<G::PKType>(2, &[DkgBehavior::Honest; 5]);
+    let pk = old[0].public_key();
+    let old_partials: Vec<_> = old
+        .iter()
+        .map(|o| G::partial_sign(o.secret_share(), msg))
+        .collect();
+    let expect = G::combine(&old_partials[2..]).unwrap();
+
+    // old members 1, 3, 4, and 5 move the key from a (2, 5) to a (3, 4) committee. Member 2
+    // is offline, member 5 deals a polynomial with the wrong constant term, and member 3
+    // sends new member 2 a bad share and then answers the complaint with another.
+    let mut dealers: Vec<_> = [0, 2, 3, 4]
+        .iter()
+        .map(|&i| ReshareDealer::new(old[i].secret_share(), 3, 4).unwrap())
+        .collect();
+    let mut recipients: Vec<_> = (1..=4u32)
+        .map(|j| ReshareRecipient::new(j, 3, 4, old[0].commitments(), 5).unwrap())
+        .collect();
+    let mut commitments = Vec::new();
+    let mut shares = Vec::new();
+    for d in dealers.iter_mut() {
+        let (commitment, dealt): (DkgCommitment<G::PKType>, _) = d.deal(&mut rng);
+        if d.index() == 5 {
+            let mut coeffs = commitment.coefficients().to_vec();
+            coeffs[0].add_assign(&G::PKType::one());
+            commitments.push(DkgCommitment::new(5, coeffs).unwrap());
+        } else {
+            commitments.push(commitment);
+        }
+        for share in dealt {
+            if d.index() == 3 && share.recipient() == 2 {
+                shares.push(DkgShare::new(3, 2, Fr::one()).unwrap());
+            } else {
+                shares.push(share);
+            }
+        }
+    }
+    for r in recipients.iter_mut() {
+        for c in &commitments {
+            assert_eq!(r.receive_commitment(c.clone()).is_err(), c.dealer() == 5);
+        }
+    }
+    for share in shares {
+        let corrupt = share.dealer() == 5 || (share.dealer(), share.recipient()) == (3, 2);
+        let r = &mut recipients[share.recipient() as usize - 1];
+        assert_eq!(r.receive_share(share).is_err(), corrupt);
+    }
+    let complaints: Vec<_> = recipients.iter().flat_map(|r| r.complaints()).collect();
+    assert_eq!(complaints, vec![DkgComplaint::new(3, 2).unwrap()]);
+    for c in &complaints {
+        for d in dealers.iter_mut() {
+            d.receive_complaint(*c).unwrap();
+        }
+        for r in recipients.iter_mut() {
+            r.receive_complaint(*c).unwrap();
+        }
+    }
+    let answers: Vec<_> = dealers
+        .iter()
+        .flat_map(|d| d.responses())
+        .map(|a| DkgShare::new(a.dealer(), a.recipient(), Fr::one()).unwrap())
+        .collect();
+    assert_eq!(answers.len(), 1);
+    for r in recipients.iter_mut() {
+        for a in &answers {
+            assert_eq!(r.receive_response(a.clone()), Err(BlsError::InvalidShare));
+        }
+    }
+    let new: Vec<_> = recipients
+        .into_iter()
+        .map(|r| r.finish().unwrap())
+        .collect();
+
+    // the new committee holds the same key with the new threshold
+    assert_eq!(new[0].qualified(), &[1, 4]);
+    assert_eq!(new[0].commitments().len(), 3);
+    for n in &new {
+        assert_eq!(n.public_key(), pk);
+        assert_eq!(n.commitments(), new[0].commitments());
+        assert_eq!(
+            &G::public_key_share(n.secret_share()),
+            &new[0].public_key_share(n.secret_share().index()).unwrap()
+        );
+    }
+    let partials: Vec<_> = new
+        .iter()
+        .map(|o| G::partial_sign(o.secret_share(), msg))
+        .collect();
+    for subset in &[vec![0, 1, 2], vec![3, 1, 0], vec![0, 1, 2, 3]] {
+        let some: Vec<_> = subset.iter().map(|&i| partials[i]).collect();
+        let sig = G::combine(&some).unwrap();
+        assert_eq!(sig, expect);
+        assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+            pk, sig, msg
+        ));
+    }
+    assert!(!<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        pk,
+        G::combine(&partials[..2]).unwrap(),
+        msg
+    ));
+}
+
+#[test]
+fn test_refresh() {
+    test_refresh_suite::<G1>();
+    test_refresh_suite::<G2>();
+}
+
+#[test]
+fn test_reshare() {
+    test_reshare_suite::<G1>();
+    test_reshare_suite::<G2>();
+
+    // fewer than the old threshold of old members cannot reshare
+    let mut rng = thread_rng();
+    let old = run_dkg::<G2>(3, &[DkgBehavior::Honest; 4]);
+    let mut recipient = ReshareRecipient::new(1, 1, 1, old[0].commitments(), 4).unwrap();
+    for o in &old[..2] {
+        let mut dealer = ReshareDealer::new(o.secret_share(), 1, 1).unwrap();
+        let (commitment, mut dealt) = dealer.deal(&mut rng);
+        recipient.receive_commitment(commitment).unwrap();
+        recipient.receive_share(dealt.pop().unwrap()).unwrap();
+    }
+    assert_eq!(recipient.finish().unwrap_err(), BlsError::InvalidThreshold);
+
+    // invalid parameters
+    let commitments = old[0].commitments();
+    assert_eq!(
+        ReshareRecipient::new(1, 2, 3, commitments, 2).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        ReshareRecipient::<G2>::new(1, 2, 3, &[], 2).unwrap_err(),
+        BlsError::InvalidCommitment
+    );
+    assert_eq!(
+        ReshareRecipient::new(4, 2, 3, commitments, 4).unwrap_err(),
+        BlsError::InvalidSignerIndex
+    );
+    assert_eq!(
+        ReshareDealer::new(old[0].secret_share(), 3, 2).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        ShareRefresh::new(&old[3], 3).unwrap_err(),
+        BlsError::InvalidSignerIndex
+    );
+    assert_eq!(
+        ShareRefresh::new(&old[0], 2).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+}
+
+fn test_coordinator_suite<G: BLSSignatureThreshold<ExpandMsgXmd<Sha256>> + PointEncoding>() {
+    let (msg_a, msg_b) = ("the first request", "the second request");
+    let (secret, pk) = G::keygen("the coordinated key");
+    let shares = split_secret(&secret, 3, 5, &mut thread_rng()).unwrap();
+    let pk_shares: Vec<_> = shares.iter().map(G::public_key_share).collect();
+    let sign = |i: usize, msg: &str| G::partial_sign(&shares[i], msg).to_bytes();
+    let mut coord = ThresholdCoordinator::<G, ExpandMsgXmd<Sha256>>::new(3, &pk_shares).unwrap();
+    assert_eq!(coord.status(msg_a), None);
+    // an invalid partial does not start tracking a message
+    assert_eq!(
+        coord.receive(msg_a, &sign(4, msg_b)),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(coord.status(msg_a), None);
+
+    // partials for both messages arrive interleaved; bad ones are dropped
+    assert_eq!(
+        coord.receive(msg_a, &sign(0, msg_a)),
+        Ok(SigningStatus::Pending(1))
+    );
+    assert_eq!(
+        coord.receive(msg_b, &sign(2, msg_b)),
+        Ok(SigningStatus::Pending(1))
+    );
+    assert_eq!(
+        coord.receive(msg_a, &sign(1, msg_b)),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        coord.receive(msg_a, &sign(0, msg_a)),
+        Err(BlsError::DuplicateSigner)
+    );
+    assert_eq!(
+        coord.receive(msg_a, &sign(0, msg_a)[1..]),
+        Err(BlsError::InvalidEncoding)
+    );
+    let unknown = PartialSignature::new(9, *G::partial_sign(&shares[0], msg_a).as_point());
+    assert_eq!(
+        coord.receive(msg_a, &unknown.unwrap().to_bytes()),
+        Err(BlsError::InvalidSignerIndex)
+    );
+    assert_eq!(
+        coord.receive(msg_b, &sign(4, msg_a)),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        coord.receive(msg_a, &sign(3, msg_a)),
+        Ok(SigningStatus::Pending(2))
+    );
+    assert_eq!(coord.status(msg_a), Some(SigningStatus::Pending(2)));
+
+    // signer 2 recovers, and the third valid partial completes the signature
+    let expect = <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(secret, msg_a);
+    assert_eq!(
+        coord.receive(msg_a, &sign(1, msg_a)),
+        Ok(SigningStatus::Complete(expect))
+    );
+    // later partials, even invalid ones, are ignored
+    assert_eq!(
+        coord.receive(msg_a, &sign(4, msg_b)),
+        Ok(SigningStatus::Complete(expect))
+    );
+    assert_eq!(coord.status(msg_a), Some(SigningStatus::Complete(expect)));
+    let sig = coord.finish(msg_a).unwrap();
+    assert!(<G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::verify(
+        pk, sig, msg_a
+    ));
+    assert_eq!(coord.status(msg_a), None);
+
+    // signer 5 recovers on the second message, and signer 1 sends a bad partial
+    assert_eq!(
+        coord.receive(msg_b, &sign(4, msg_b)),
+        Ok(SigningStatus::Pending(2))
+    );
+    assert_eq!(
+        coord.receive(msg_b, &sign(0, msg_a)),
+        Err(BlsError::PairingCheckFailed)
+    );
+
+    // the second message never gets enough valid partials
+    let report = coord.finish(msg_b).unwrap_err();
+    assert_eq!(report.valid(), &[3, 5]);
+    assert_eq!(report.invalid(), &[1]);
+    assert_eq!(coord.status(msg_b), None);
+    let report = coord.finish("never requested").unwrap_err();
+    assert!(report.valid().is_empty() && report.invalid().is_empty());
+}
+
+#[test]
+fn test_coordinator() {
+    test_coordinator_suite::<G1>();
+    test_coordinator_suite::<G2>();
+
+    let shares = split_secret(&xprime_from_sk("a key"), 2, 3, &mut thread_rng()).unwrap();
+    let pk_shares: Vec<_> = shares.iter().map(G1::public_key_share).collect();
+    type Coord = ThresholdCoordinator<G1, ExpandMsgXmd<Sha256>>;
+    assert_eq!(
+        Coord::new(0, &pk_shares).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        Coord::new(4, &pk_shares).unwrap_err(),
+        BlsError::InvalidThreshold
+    );
+    assert_eq!(
+        Coord::new(2, &[pk_shares[0], pk_shares[1], pk_shares[0]]).unwrap_err(),
+        BlsError::DuplicateSigner
+    );
+    let zero = PublicKeyShare::new(4, G2::zero()).unwrap();
+    assert_eq!(
+        Coord::new(2, &[pk_shares[0], zero]).unwrap_err(),
+        BlsError::IdentityPoint
+    );
+}
+
+fn test_vrf_suite<G>()
+where
+    G: BLSVrf<ExpandMsgXmd<Sha256>> + BLSSignatureBasic<ExpandMsgXmd<Sha256>>,
+{
+    let (x_prime, pk) = G::keygen("the leader election key");
+    let (beta, proof) = G::vrf_prove(x_prime, "round 1");
+    assert_eq!(G::vrf_verify(pk, "round 1", proof), Ok(beta));
+    assert_eq!(G::vrf_proof_to_hash(&proof), beta);
+    // the proof is a signature under the VRF tag, and the output is deterministic
+    assert_eq!(
+        proof,
+        <G as BLSSigCore<ExpandMsgXmd<Sha256>>>::core_sign(
+            x_prime,
+            "round 1",
+            <G as BLSVrf<ExpandMsgXmd<Sha256>>>::CSUITE
+        )
+    );
+    assert_eq!(G::vrf_prove(x_prime, "round 1"), (beta, proof));
+    // a Basic signature on the same input is not a proof
+    let basic = <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::sign(x_prime, "round 1");
+    assert_ne!(basic, proof);
+    assert_eq!(
+        G::vrf_verify(pk, "round 1", basic),
+        Err(BlsError::PairingCheckFailed)
+    );
+
+    let (beta2, proof2) = G::vrf_prove(x_prime, "round 2");
+    assert_ne!(beta, beta2);
+    assert_eq!(
+        G::vrf_verify(pk, "round 1", proof2),
+        Err(BlsError::PairingCheckFailed)
+    );
+    let (_, other_pk) = G::keygen("another key");
+    assert_eq!(
+        G::vrf_verify(other_pk, "round 1", proof),
+        Err(BlsError::PairingCheckFailed)
+    );
+    assert_eq!(
+        G::vrf_verify(G::PKType::zero(), "round 1", G::zero()),
+        Err(BlsError::IdentityPoint)
+    );
+    // the output is not the hash of the proof under any other tag
+    assert_ne!(
+        &beta[..],
+        &ExpandMsgXmd::<Sha256>::expand_message(
+            proof.to_compressed().as_ref(),
+            <G as BLSSignatureBasic<ExpandMsgXmd<Sha256>>>::CSUITE,
+            32
+        )[..]
+    );
+}
+
+#[test]
+fn test_vrf() {
+    test_vrf_suite::<G1>();
+    test_vrf_suite::<G2>();
+}
@@ -0,0 +1,334 @@
+/*!
+Threshold signatures with Shamir-shared secret keys
+
+A dealer splits a secret key x' into n shares so that any t of them can sign: share i is
+f(i), where f is a random polynomial of degree t - 1 with f(0) = x'. Each share signs
+like an ordinary secret key, under the Basic scheme's ciphersuite tag, and t partial
+signatures combine, by Lagrange interpolation in the exponent, into exactly the
+signature that x' would have produced. The result verifies under the group public key
+with the ordinary `BLSSignatureBasic::verify`; verifiers need not know that the key was
+shared.
+*/
+
+use encoding::PointEncoding;
+use error::BlsError;
+use ff::{Field, PrimeField};
+use pairing_plus::bls12_381::{Fr, G1, G2};
+use pairing_plus::hash_to_field::{BaseFromRO, ExpandMsg, ExpandMsgXmd};
+use pairing_plus::CurveProjective;
+use rand::Rng;
+use sha2::digest::generic_array::GenericArray;
+use sha2::Sha256;
+use signature::{_msm_help, BLSSignatureBasic};
+use std::collections::HashSet;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Share `index` of a secret key: the value at `index` of the dealer's polynomial.
+/// Indices start at 1, since the value at 0 is the secret itself.
+/// The value is zeroized on drop, and this type is deliberately not `Copy`, but
+/// `partial_sign` hands a copy of it to `BLSSignatureBasic::sign`, which takes the scalar
+/// by value and may leave it on the stack uncleared.
+pub struct SecretShare {
+    index: u32,
+    value: Fr,
+}
+
+impl SecretShare {
+    /// A share with the given index and value. Fails if `index` is zero.
+    pub fn new(index: u32, value: Fr) -> Result<Self, BlsError> {
+        if index == 0 {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        Ok(SecretShare { index, value })
+    }
+
+    /// The index of this share
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The value of this share
+    pub fn value(&self) -> &Fr {
+        &self.value
+    }
+}
+
+impl Clone for SecretShare {
+    fn clone(&self) -> Self {
+        SecretShare {
+            index: self.index,
+            value: self.value,
+        }
+    }
+}
+
+impl Zeroize for SecretShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for SecretShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl PartialEq for SecretShare {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.value == other.value
+    }
+}
+
+impl Eq for SecretShare {}
+
+impl fmt::Debug for SecretShare {
+    // never print the share's value
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretShare({}, <redacted>)", self.index)
+    }
+}
+
+/// The public key of share `index`: the public key group's generator times the share
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKeyShare<P: CurveProjective> {
+    index: u32,
+    pk: P,
+}
+
+impl<P: CurveProjective> PublicKeyShare<P> {
+    /// A public key share with the given index. Fails if `index` is zero.
+    pub fn new(index: u32, pk: P) -> Result<Self, BlsError> {
+        if index == 0 {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        Ok(PublicKeyShare { index, pk })
+    }
+
+    /// The index of the share
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The public key
+    pub fn as_point(&self) -> &P {
+        &self.pk
+    }
+}
+
+/// A signature by share `index`, in the signature group `G`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialSignature<G: CurveProjective> {
+    index: u32,
+    sig: G,
+}
+
+impl<G: CurveProjective> PartialSignature<G> {
+    /// A partial signature with the given index. Fails if `index` is zero.
+    pub fn new(index: u32, sig: G) -> Result<Self, BlsError> {
+        if index == 0 {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        Ok(PartialSignature { index, sig })
+    }
+
+    /// The index of the share that signed
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The signature
+    pub fn as_point(&self) -> &G {
+        &self.sig
+    }
+}
+
+impl<G: PointEncoding> PartialSignature<G> {
+    /// Serialize as the index, a 4-byte big-endian integer, followed by the compressed
+    /// signature: 52 bytes in G1, 100 bytes in G2
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let sig = self.sig.to_compressed();
+        let mut ret = Vec::with_capacity(4 + sig.as_ref().len());
+        ret.extend_from_slice(&self.index.to_be_bytes());
+        ret.extend_from_slice(sig.as_ref());
+        ret
+    }
+
+    /// Deserialize the output of `to_bytes`. Fails if the length is wrong, if the index
+    /// is zero, or if the signature does not decode.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let mut enc = G::zero().to_compressed();
+        if bytes.len() != 4 + enc.as_ref().len() {
+            return Err(BlsError::InvalidEncoding);
+        }
+        let mut index = [0u8; 4];
+        index.copy_from_slice(&bytes[..4]);
+        enc.as_mut().copy_from_slice(&bytes[4..]);
+        Self::new(u32::from_be_bytes(index), G::from_compressed(&enc)?)
+    }
+}
+
+/// Split `secret`, e.g., the output of `xprime_from_sk`, into `n` shares with indices
+/// 1 through `n`, any `threshold` of which can sign.
+/// Fails unless 1 <= `threshold` <= `n` < 2^32.
+pub fn split_secret<R: Rng>(
+    secret: &Fr,
+    threshold: usize,
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<SecretShare>, BlsError> {
+    if threshold == 0 || threshold > n || n > u32::MAX as usize {
+        return Err(BlsError::InvalidThreshold);
+    }
+    let mut coeffs = Vec::with_capacity(threshold);
+    coeffs.push(*secret);
+    for _ in 1..threshold {
+        coeffs.push(_random_scalar(rng));
+    }
+    let ret = (1..=n as u32)
+        .map(|i| SecretShare {
+            index: i,
+            value: _poly_eval(&coeffs[..], i),
+        })
+        .collect();
+    for c in coeffs.iter_mut() {
+        c.zeroize();
+    }
+    Ok(ret)
+}
+
+// uniformly random scalar, up to a bias of 2^-128: reduce 48 random bytes mod r
+pub(crate) fn _random_scalar<R: Rng>(rng: &mut R) -> Fr {
+    let mut okm = GenericArray::default();
+    rng.fill_bytes(&mut okm[..]);
+    let ret = Fr::from_okm(&okm);
+    okm.as_mut_slice().zeroize();
+    ret
+}
+
+// the polynomial with coefficients `coeffs`, lowest degree first, evaluated at x
+pub(crate) fn _poly_eval(coeffs: &[Fr], x: u32) -> Fr {
+    let x = Fr::from_repr(u64::from(x).into()).unwrap();
+    let mut ret = Fr::zero();
+    for c in coeffs.iter().rev() {
+        ret.mul_assign(&x);
+        ret.add_assign(c);
+    }
+    ret
+}
+
+// the Lagrange coefficients for interpolating at 0 from the values at `indices`
+pub(crate) fn _lagrange_at_zero(indices: &[u32]) -> Result<Vec<Fr>, BlsError> {
+    if indices.is_empty() {
+        return Err(BlsError::EmptyInput);
+    }
+    if indices.contains(&0) {
+        return Err(BlsError::InvalidSignerIndex);
+    }
+    if indices.iter().collect::<HashSet<_>>().len() != indices.len() {
+        return Err(BlsError::DuplicateSigner);
+    }
+    let xs: Vec<Fr> = indices
+        .iter()
+        .map(|&i| Fr::from_repr(u64::from(i).into()).unwrap())
+        .collect();
+    // lambda_j = prod_{m != j} x_m / (x_m - x_j)
+    Ok(xs
+        .iter()
+        .enumerate()
+        .map(|(j, x_j)| {
+            let mut num = Fr::one();
+            let mut den = Fr::one();
+            for (m, x_m) in xs.iter().enumerate() {
+                if m != j {
+                    num.mul_assign(x_m);
+                    let mut diff = *x_m;
+                    diff.sub_assign(x_j);
+                    den.mul_assign(&diff);
+                }
+            }
+            // the indices are distinct, so den is nonzero
+            num.mul_assign(&den.inverse().unwrap());
+            num
+        })
+        .collect())
+}
+
+// sum_j lambda_j * points[j], interpolating at 0 from the points at `indices`
+pub(crate) fn _interpolate_help<T: CurveProjective>(
+    indices: &[u32],
+    points: &[T],
+) -> Result<T, BlsError> {
+    let scalars: Vec<[u64; 4]> = _lagrange_at_zero(indices)?
+        .iter()
+        .map(|l| l.into_repr().0)
+        .collect();
+    Ok(_msm_help(points, &scalars[..]))
+}
+
+/// Threshold signing with secret shares, using the Basic scheme's ciphersuite tag
+pub trait BLSSignatureThreshold<X: ExpandMsg>:
+    BLSSignatureBasic<X> + CurveProjective<Scalar = Fr>
+{
+    /// The public key of a secret share
+    fn public_key_share(share: &SecretShare) -> PublicKeyShare<Self::PKType> {
+        let mut pk = Self::PKType::one();
+        pk.mul_assign(share.value);
+        PublicKeyShare {
+            index: share.index,
+            pk,
+        }
+    }
+
+    /// Sign a message with a secret share
+    fn partial_sign<B: AsRef<[u8]>>(share: &SecretShare, msg: B) -> PartialSignature<Self> {
+        PartialSignature {
+            index: share.index,
+            sig: <Self as BLSSignatureBasic<X>>::sign(share.value, msg),
+        }
+    }
+
+    /// Verify a partial signature against the public key of the share that made it
+    fn partial_verify<B: AsRef<[u8]>>(
+        pk_share: &PublicKeyShare<Self::PKType>,
+        partial: &PartialSignature<Self>,
+        msg: B,
+    ) -> bool {
+        Self::try_partial_verify(pk_share, partial, msg).is_ok()
+    }
+
+    /// Like `partial_verify`, but reports why verification failed
+    fn try_partial_verify<B: AsRef<[u8]>>(
+        pk_share: &PublicKeyShare<Self::PKType>,
+        partial: &PartialSignature<Self>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        if pk_share.index != partial.index {
+            return Err(BlsError::InvalidSignerIndex);
+        }
+        <Self as BLSSignatureBasic<X>>::try_verify(pk_share.pk, partial.sig, msg)
+    }
+
+    /// Combine partial signatures on one message, from distinct shares, into a signature
+    /// under the group public key. Given at least `threshold` valid partials the result
+    /// is the signature of the shared secret; given fewer it will not verify.
+    fn combine(partials: &[PartialSignature<Self>]) -> Result<Self, BlsError> {
+        let indices: Vec<u32> = partials.iter().map(|p| p.index).collect();
+        let sigs: Vec<Self> = partials.iter().map(|p| p.sig).collect();
+        _interpolate_help(&indices[..], &sigs[..])
+    }
+
+    /// Recover the group public key from the public keys of at least `threshold` shares
+    fn combine_public_keys(
+        pk_shares: &[PublicKeyShare<Self::PKType>],
+    ) -> Result<Self::PKType, BlsError> {
+        let indices: Vec<u32> = pk_shares.iter().map(|p| p.index).collect();
+        let pks: Vec<Self::PKType> = pk_shares.iter().map(|p| p.pk).collect();
+        _interpolate_help(&indices[..], &pks[..])
+    }
+}
+
+impl BLSSignatureThreshold<ExpandMsgXmd<Sha256>> for G1 {}
+
+impl BLSSignatureThreshold<ExpandMsgXmd<Sha256>> for G2 {}
@@ -0,0 +1,657 @@
+/*!
+Typed wrappers for BLS secret keys, public keys, and signatures
+*/
+
+use ciphersuite::{
+    Ciphersuite, G1XmdSha256Aug, G1XmdSha256Basic, G1XmdSha256Pop, G2XmdSha256Aug,
+    G2XmdSha256Basic, G2XmdSha256Pop, PkT, PopCiphersuite,
+};
+use encoding::{sk_from_bytes, sk_to_bytes, PointEncoding};
+use error::BlsError;
+use hashed::HashedMessage;
+use pairing_plus::bls12_381::{Fr, G1, G2};
+use pairing_plus::serdes::SerDes;
+use pairing_plus::CurveProjective;
+use prepared::PreparedPublicKey;
+use signature::{key_gen, weighted_aggregate, KeyGenMode};
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::marker::PhantomData;
+use zeroize::Zeroize;
+
+/// Alias for the scalar type corresponding to a CurveProjective type
+type ScalarT<PtT> = <PtT as CurveProjective>::Scalar;
+
+/// Marker type for the 'basic' scheme
+#[derive(Debug)]
+pub enum Basic {}
+
+/// Marker type for the message augmentation scheme
+#[derive(Debug)]
+pub enum Aug {}
+
+/// Marker type for the proof of possession scheme
+#[derive(Debug)]
+pub enum Pop {}
+
+/// A BLS signature scheme with signatures in the group `G`, as used by the typed
+/// wrappers. Every `Ciphersuite` is a `Scheme` for its own group. The markers `Basic`,
+/// `Aug`, and `Pop` stand for the standard suites with expand_message_xmd and SHA-256,
+/// e.g., `Basic` in G1 is `G1XmdSha256Basic`.
+pub trait Scheme<G: CurveProjective>: 'static {
+    /// The ciphersuite that signs and verifies
+    type Suite: Ciphersuite<Group = G>;
+}
+
+impl Scheme<G1> for Basic {
+    type Suite = G1XmdSha256Basic;
+}
+
+impl Scheme<G2> for Basic {
+    type Suite = G2XmdSha256Basic;
+}
+
+impl Scheme<G1> for Aug {
+    type Suite = G1XmdSha256Aug;
+}
+
+impl Scheme<G2> for Aug {
+    type Suite = G2XmdSha256Aug;
+}
+
+impl Scheme<G1> for Pop {
+    type Suite = G1XmdSha256Pop;
+}
+
+impl Scheme<G2> for Pop {
+    type Suite = G2XmdSha256Pop;
+}
+
+impl<C: Ciphersuite> Scheme<C::Group> for C {
+    type Suite = C;
+}
+
+// serialize a point in compressed form
+fn _ser_help<T: SerDes>(pt: &T) -> Vec<u8> {
+    let mut cur = Cursor::new(Vec::<u8>::new());
+    // writing to a Vec cannot fail
+    pt.serialize(&mut cur, true).unwrap();
+    cur.into_inner()
+}
+
+// deserialize a compressed point, rejecting trailing bytes
+fn _deser_help<T: SerDes>(bytes: &[u8]) -> Result<T, BlsError> {
+    let mut cur = Cursor::new(bytes);
+    let ret = T::deserialize(&mut cur, true)?;
+    if cur.position() as usize != bytes.len() {
+        return Err(BlsError::InvalidEncoding);
+    }
+    Ok(ret)
+}
+
+/// A secret key for scheme `S` with signatures in group `G`.
+/// The secret exponent is zeroized on drop, and this type is deliberately not `Copy`.
+/// That only covers the copy held here: the scalar type itself is `Copy`, and the signing
+/// functions in `Ciphersuite` and `BLSSigCore` take it by value, so each signature can
+/// leave a copy of the exponent on the stack that is not cleared.
+pub struct SecretKey<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> {
+    x_prime: ScalarT<G>,
+    _scheme: PhantomData<(G, S)>,
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> SecretKey<G, S> {
+    /// Derive a secret key from the secret key bytes, as in `BLSSigCore::keygen`
+    pub fn new<B: AsRef<[u8]>>(sk: B) -> Self {
+        // HKDF-Expand only fails when asked for more than 255 * HashLen bytes
+        Self::try_new(sk).unwrap()
+    }
+
+    /// Like `new`, but returns an error instead of panicking
+    pub fn try_new<B: AsRef<[u8]>>(sk: B) -> Result<Self, BlsError> {
+        Ok(Self::from_scalar(S::Suite::try_keygen(sk)?.0))
+    }
+
+    /// Wrap an existing secret exponent
+    pub fn from_scalar(x_prime: ScalarT<G>) -> Self {
+        SecretKey {
+            x_prime,
+            _scheme: PhantomData,
+        }
+    }
+
+    /// The secret exponent x_prime
+    pub fn as_scalar(&self) -> &ScalarT<G> {
+        &self.x_prime
+    }
+
+    /// The public key corresponding to this secret key
+    pub fn public_key(&self) -> PublicKey<G, S> {
+        let mut pk = PkT::<S::Suite>::one();
+        pk.mul_assign(self.x_prime);
+        PublicKey::from_point(pk)
+    }
+
+    /// Sign a message
+    pub fn sign<B: AsRef<[u8]>>(&self, msg: B) -> Signature<G, S> {
+        // serializing into a Vec cannot fail
+        self.try_sign(msg).unwrap()
+    }
+
+    /// Like `sign`, but returns an error instead of panicking
+    pub fn try_sign<B: AsRef<[u8]>>(&self, msg: B) -> Result<Signature<G, S>, BlsError> {
+        S::Suite::try_sign(self.x_prime, msg).map(Signature::from_point)
+    }
+
+    /// Sign a message that was already hashed; see `Ciphersuite::sign_hashed`
+    pub fn sign_hashed(&self, hashed: &HashedMessage<G, S::Suite>) -> Signature<G, S> {
+        Signature::from_point(S::Suite::sign_hashed(self.x_prime, hashed))
+    }
+}
+
+impl<G: CurveProjective<Scalar = Fr>, S: Scheme<G>> SecretKey<G, S> {
+    /// Derive a secret key from input keying material and key_info, as in `key_gen`
+    pub fn key_gen<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        ikm: B,
+        key_info: C,
+        mode: KeyGenMode,
+    ) -> Result<Self, BlsError> {
+        key_gen(ikm, key_info, mode).map(Self::from_scalar)
+    }
+
+    /// Serialize the secret exponent as a 32-byte big-endian integer
+    pub fn to_bytes(&self) -> [u8; 32] {
+        sk_to_bytes(&self.x_prime)
+    }
+
+    /// Deserialize a secret exponent; fails unless the value is in [1, r - 1]
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, BlsError> {
+        sk_from_bytes(bytes).map(Self::from_scalar)
+    }
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> Clone for SecretKey<G, S> {
+    fn clone(&self) -> Self {
+        Self::from_scalar(self.x_prime)
+    }
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> Zeroize for SecretKey<G, S> {
+    fn zeroize(&mut self) {
+        self.x_prime.zeroize();
+    }
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> Drop for SecretKey<G, S> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> PartialEq for SecretKey<G, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x_prime == other.x_prime
+    }
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> Eq for SecretKey<G, S> {}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> fmt::Debug for SecretKey<G, S> {
+    // never print the secret exponent
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretKey(<redacted>)")
+    }
+}
+
+impl<G: CurveProjective<Scalar: Zeroize>, S: Scheme<G>> SecretKey<G, S>
+where
+    S::Suite: PopCiphersuite,
+{
+    /// Prove possession of the corresponding public key
+    pub fn pop_prove(&self) -> Signature<G, S> {
+        Signature::from_point(S::Suite::pop_prove(self.x_prime))
+    }
+}
+
+/// A public key for scheme `S` with signatures in group `G`
+pub struct PublicKey<G: CurveProjective, S: Scheme<G>> {
+    point: PkT<S::Suite>,
+    _scheme: PhantomData<(G, S)>,
+}
+
+impl<G: CurveProjective, S: Scheme<G>> PublicKey<G, S> {
+    /// Wrap an existing public key group element
+    pub fn from_point(point: PkT<S::Suite>) -> Self {
+        PublicKey {
+            point,
+            _scheme: PhantomData,
+        }
+    }
+
+    /// The underlying group element
+    pub fn as_point(&self) -> &PkT<S::Suite> {
+        &self.point
+    }
+
+    /// Verify a signature on a message under this public key
+    pub fn verify<B: AsRef<[u8]>>(&self, sig: &Signature<G, S>, msg: B) -> bool {
+        self.try_verify(sig, msg).is_ok()
+    }
+
+    /// Like `verify`, but reports why verification failed
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        sig: &Signature<G, S>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        S::Suite::try_verify(self.point, sig.point, msg)
+    }
+
+    /// Like `try_verify`, but skips `key_validate`.
+    /// Only use this for keys that were already validated, e.g., at registration time.
+    pub fn try_verify_prevalidated<B: AsRef<[u8]>>(
+        &self,
+        sig: &Signature<G, S>,
+        msg: B,
+    ) -> Result<(), BlsError> {
+        S::Suite::try_verify_prevalidated(self.point, sig.point, msg)
+    }
+
+    /// KeyValidate: check that this key is not the identity and is in the prime-order subgroup
+    pub fn key_validate(&self) -> Result<(), BlsError> {
+        S::Suite::key_validate(&self.point)
+    }
+
+    /// Serialize in compressed form
+    pub fn to_bytes(&self) -> Vec<u8> {
+        _ser_help(&self.point)
+    }
+
+    /// Deserialize from compressed form; the result is guaranteed to pass `key_validate`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        let ret = _deser_help(bytes).map(Self::from_point)?;
+        ret.key_validate()?;
+        Ok(ret)
+    }
+
+    /// Serialize in compressed form: 48 bytes in G1, 96 bytes in G2
+    pub fn to_compressed(&self) -> <PkT<S::Suite> as PointEncoding>::Compressed {
+        self.point.to_compressed()
+    }
+
+    /// Serialize in uncompressed form: 96 bytes in G1, 192 bytes in G2
+    pub fn to_uncompressed(&self) -> <PkT<S::Suite> as PointEncoding>::Uncompressed {
+        self.point.to_uncompressed()
+    }
+
+    /// Deserialize from compressed form; the result is guaranteed to pass `key_validate`
+    pub fn from_compressed(
+        bytes: &<PkT<S::Suite> as PointEncoding>::Compressed,
+    ) -> Result<Self, BlsError> {
+        let ret = Self::from_point(PkT::<S::Suite>::from_compressed(bytes)?);
+        ret.key_validate()?;
+        Ok(ret)
+    }
+
+    /// Deserialize from uncompressed form; the result is guaranteed to pass `key_validate`
+    pub fn from_uncompressed(
+        bytes: &<PkT<S::Suite> as PointEncoding>::Uncompressed,
+    ) -> Result<Self, BlsError> {
+        let ret = Self::from_point(PkT::<S::Suite>::from_uncompressed(bytes)?);
+        ret.key_validate()?;
+        Ok(ret)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> PublicKey<G, S> {
+    /// Verify a signature on a message that was already hashed
+    pub fn verify_hashed(
+        &self,
+        sig: &Signature<G, S>,
+        hashed: &HashedMessage<G, S::Suite>,
+    ) -> bool {
+        self.try_verify_hashed(sig, hashed).is_ok()
+    }
+
+    /// Like `verify_hashed`, but reports why verification failed
+    pub fn try_verify_hashed(
+        &self,
+        sig: &Signature<G, S>,
+        hashed: &HashedMessage<G, S::Suite>,
+    ) -> Result<(), BlsError> {
+        S::Suite::try_verify_hashed(self.point, sig.point, hashed)
+    }
+
+    /// Like `try_verify_hashed`, but skips `key_validate`
+    pub fn try_verify_hashed_prevalidated(
+        &self,
+        sig: &Signature<G, S>,
+        hashed: &HashedMessage<G, S::Suite>,
+    ) -> Result<(), BlsError> {
+        S::Suite::try_verify_hashed_prevalidated(self.point, sig.point, hashed)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> PublicKey<G, S>
+where
+    S::Suite: PopCiphersuite,
+{
+    /// Check a proof of possession of this public key
+    pub fn pop_verify(&self, proof: &Signature<G, S>) -> bool {
+        self.try_pop_verify(proof).is_ok()
+    }
+
+    /// Like `pop_verify`, but reports why verification failed
+    pub fn try_pop_verify(&self, proof: &Signature<G, S>) -> Result<(), BlsError> {
+        S::Suite::try_pop_verify(self.point, proof.point)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Clone for PublicKey<G, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Copy for PublicKey<G, S> {}
+
+impl<G: CurveProjective, S: Scheme<G>> PartialEq for PublicKey<G, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Eq for PublicKey<G, S> {}
+
+impl<G: CurveProjective, S: Scheme<G>> Hash for PublicKey<G, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> fmt::Debug for PublicKey<G, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PublicKey").field(&self.point).finish()
+    }
+}
+
+/// A signature for scheme `S` in group `G`
+pub struct Signature<G: CurveProjective, S: Scheme<G>> {
+    point: G,
+    _scheme: PhantomData<S>,
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Signature<G, S> {
+    /// Wrap an existing signature group element
+    pub fn from_point(point: G) -> Self {
+        Signature {
+            point,
+            _scheme: PhantomData,
+        }
+    }
+
+    /// The underlying group element
+    pub fn as_point(&self) -> &G {
+        &self.point
+    }
+
+    /// Aggregate signatures
+    pub fn aggregate(sigs: &[Self]) -> Self {
+        let mut ret = G::zero();
+        for sig in sigs {
+            ret.add_assign(&sig.point);
+        }
+        Self::from_point(ret)
+    }
+
+    /// Aggregate signatures with integer weights, e.g., stake: sum_i weights[i] * sigs[i]
+    pub fn weighted_aggregate(sigs: &[Self], weights: &[u64]) -> Result<Self, BlsError> {
+        let sigs: Vec<G> = sigs.iter().map(|sig| sig.point).collect();
+        weighted_aggregate(&sigs[..], weights).map(Self::from_point)
+    }
+
+    /// Verify this aggregated signature on `msgs` under `pks`
+    pub fn aggregate_verify<B: AsRef<[u8]>>(&self, pks: &[PublicKey<G, S>], msgs: &[B]) -> bool {
+        self.try_aggregate_verify(pks, msgs).is_ok()
+    }
+
+    /// Like `aggregate_verify`, but reports why verification failed
+    pub fn try_aggregate_verify<B: AsRef<[u8]>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> Result<(), BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        S::Suite::try_aggregate_verify(&pks[..], msgs, self.point)
+    }
+
+    /// Like `try_aggregate_verify`, but skips `key_validate` on `pks`
+    pub fn try_aggregate_verify_prevalidated<B: AsRef<[u8]>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> Result<(), BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        S::Suite::try_aggregate_verify_prevalidated(&pks[..], msgs, self.point)
+    }
+
+    /// Verify each of `sigs` on the corresponding message in `msgs` under the
+    /// corresponding key in `pks`, as a single randomized batch
+    pub fn batch_verify<B: AsRef<[u8]>>(
+        sigs: &[Self],
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> bool {
+        Self::try_batch_verify(sigs, pks, msgs).is_ok()
+    }
+
+    /// Like `batch_verify`, but reports why verification failed
+    pub fn try_batch_verify<B: AsRef<[u8]>>(
+        sigs: &[Self],
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> Result<(), BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        let sigs: Vec<G> = sigs.iter().map(|sig| sig.point).collect();
+        S::Suite::try_batch_verify(&pks[..], msgs, &sigs[..])
+    }
+
+    /// Like `try_batch_verify`, but skips `key_validate` on `pks`
+    pub fn try_batch_verify_prevalidated<B: AsRef<[u8]>>(
+        sigs: &[Self],
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> Result<(), BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        let sigs: Vec<G> = sigs.iter().map(|sig| sig.point).collect();
+        S::Suite::try_batch_verify_prevalidated(&pks[..], msgs, &sigs[..])
+    }
+
+    /// Find the entries of `sigs` that are not valid signatures on the corresponding
+    /// message in `msgs` under the corresponding key in `pks`, returning their indices
+    pub fn batch_find_invalid<B: AsRef<[u8]>>(
+        sigs: &[Self],
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> Result<Vec<usize>, BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        let sigs: Vec<G> = sigs.iter().map(|sig| sig.point).collect();
+        S::Suite::batch_find_invalid(&pks[..], msgs, &sigs[..])
+    }
+
+    /// Like `batch_find_invalid`, but skips `key_validate` on `pks`
+    pub fn batch_find_invalid_prevalidated<B: AsRef<[u8]>>(
+        sigs: &[Self],
+        pks: &[PublicKey<G, S>],
+        msgs: &[B],
+    ) -> Result<Vec<usize>, BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        let sigs: Vec<G> = sigs.iter().map(|sig| sig.point).collect();
+        S::Suite::batch_find_invalid_prevalidated(&pks[..], msgs, &sigs[..])
+    }
+}
+
+impl<G: CurveProjective + SerDes, S: Scheme<G>> Signature<G, S> {
+    /// Serialize in compressed form
+    pub fn to_bytes(&self) -> Vec<u8> {
+        _ser_help(&self.point)
+    }
+
+    /// Deserialize from compressed form
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
+        _deser_help(bytes).map(Self::from_point)
+    }
+}
+
+impl<G: PointEncoding, S: Scheme<G>> Signature<G, S> {
+    /// Serialize in compressed form: 48 bytes in G1, 96 bytes in G2
+    pub fn to_compressed(&self) -> G::Compressed {
+        self.point.to_compressed()
+    }
+
+    /// Serialize in uncompressed form: 96 bytes in G1, 192 bytes in G2
+    pub fn to_uncompressed(&self) -> G::Uncompressed {
+        self.point.to_uncompressed()
+    }
+
+    /// Deserialize from compressed form; the result is in the prime-order subgroup
+    pub fn from_compressed(bytes: &G::Compressed) -> Result<Self, BlsError> {
+        G::from_compressed(bytes).map(Self::from_point)
+    }
+
+    /// Deserialize from uncompressed form; the result is in the prime-order subgroup
+    pub fn from_uncompressed(bytes: &G::Uncompressed) -> Result<Self, BlsError> {
+        G::from_uncompressed(bytes).map(Self::from_point)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Signature<G, S> {
+    /// Verify this aggregated signature on messages that were already hashed
+    pub fn aggregate_verify_hashed<H: Borrow<HashedMessage<G, S::Suite>>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        hashed: &[H],
+    ) -> bool {
+        self.try_aggregate_verify_hashed(pks, hashed).is_ok()
+    }
+
+    /// Like `aggregate_verify_hashed`, but reports why verification failed
+    pub fn try_aggregate_verify_hashed<H: Borrow<HashedMessage<G, S::Suite>>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        hashed: &[H],
+    ) -> Result<(), BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        S::Suite::try_aggregate_verify_hashed(&pks[..], hashed, self.point)
+    }
+
+    /// Like `try_aggregate_verify_hashed`, but skips `key_validate` on `pks`
+    pub fn try_aggregate_verify_hashed_prevalidated<H: Borrow<HashedMessage<G, S::Suite>>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        hashed: &[H],
+    ) -> Result<(), BlsError> {
+        let pks: Vec<PkT<S::Suite>> = pks.iter().map(|pk| pk.point).collect();
+        S::Suite::try_aggregate_verify_hashed_prevalidated(&pks[..], hashed, self.point)
+    }
+
+    /// Verify this aggregated signature on `msgs` under prepared public keys
+    pub fn aggregate_verify_prepared<P, B>(&self, pks: &[P], msgs: &[B]) -> bool
+    where
+        P: Borrow<PreparedPublicKey<G, S::Suite>>,
+        B: AsRef<[u8]>,
+    {
+        self.try_aggregate_verify_prepared(pks, msgs).is_ok()
+    }
+
+    /// Like `aggregate_verify_prepared`, but reports why verification failed
+    pub fn try_aggregate_verify_prepared<P, B>(&self, pks: &[P], msgs: &[B]) -> Result<(), BlsError>
+    where
+        P: Borrow<PreparedPublicKey<G, S::Suite>>,
+        B: AsRef<[u8]>,
+    {
+        S::Suite::try_aggregate_verify_prepared(pks, msgs, self.point)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Signature<G, S>
+where
+    S::Suite: PopCiphersuite,
+{
+    /// Verify this signature as a multisignature on `msg` by all of `pks`
+    pub fn multisig_verify<B: AsRef<[u8]>>(&self, pks: &[PublicKey<G, S>], msg: B) -> bool {
+        self.try_multisig_verify(pks, msg).is_ok()
+    }
+
+    /// Like `multisig_verify`, but reports why verification failed
+    pub fn try_multisig_verify<B: AsRef<[u8]>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        msg: B,
+    ) -> Result<(), BlsError> {
+        let pks: Vec<_> = pks.iter().map(|pk| pk.point).collect();
+        S::Suite::try_multisig_verify(&pks[..], self.point, msg)
+    }
+
+    /// Verify this signature as a weighted multisignature on `msg` by `signers`, whose
+    /// total weight must be at least `min_weight`
+    pub fn weighted_multisig_verify<B: AsRef<[u8]>>(
+        &self,
+        signers: &[(PublicKey<G, S>, u64)],
+        msg: B,
+        min_weight: u64,
+    ) -> bool {
+        self.try_weighted_multisig_verify(signers, msg, min_weight)
+            .is_ok()
+    }
+
+    /// Like `weighted_multisig_verify`, but reports why verification failed
+    pub fn try_weighted_multisig_verify<B: AsRef<[u8]>>(
+        &self,
+        signers: &[(PublicKey<G, S>, u64)],
+        msg: B,
+        min_weight: u64,
+    ) -> Result<(), BlsError> {
+        let signers: Vec<_> = signers.iter().map(|(pk, w)| (pk.point, *w)).collect();
+        S::Suite::try_weighted_multisig_verify(&signers[..], self.point, msg, min_weight)
+    }
+
+    /// Like `try_multisig_verify`, but skips `key_validate` on `pks`
+    pub fn try_multisig_verify_prevalidated<B: AsRef<[u8]>>(
+        &self,
+        pks: &[PublicKey<G, S>],
+        msg: B,
+    ) -> Result<(), BlsError> {
+        let pks: Vec<_> = pks.iter().map(|pk| pk.point).collect();
+        S::Suite::try_multisig_verify_prevalidated(&pks[..], self.point, msg)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Clone for Signature<G, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Copy for Signature<G, S> {}
+
+impl<G: CurveProjective, S: Scheme<G>> PartialEq for Signature<G, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> Eq for Signature<G, S> {}
+
+impl<G: CurveProjective + SerDes, S: Scheme<G>> Hash for Signature<G, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state)
+    }
+}
+
+impl<G: CurveProjective, S: Scheme<G>> fmt::Debug for Signature<G, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Signature").field(&self.point).finish()
+    }
+}
@@ -0,0 +1,69 @@
+/*!
+A verifiable random function from BLS signatures
+
+For a given public key and message there is exactly one valid BLS signature, so hashing
+the signature gives an output that the key's owner cannot choose but anyone can check.
+The proof for input `alpha` is a signature on `alpha` under `CSUITE`, a signing tag used
+only for VRF proofs, encoded as the compressed signature. The output is
+beta = expand_message(proof, CSUITE_VRF, 32), where `CSUITE_VRF` is a second tag used
+for nothing else. Because of the separate signing tag, an ordinary signature on `alpha`
+under the same key is not a proof and does not reveal `beta`.
+*/
+
+use encoding::PointEncoding;
+use error::BlsError;
+use pairing_plus::bls12_381::{G1, G2};
+use pairing_plus::hash_to_field::{ExpandMsg, ExpandMsgXmd};
+use pairing_plus::CurveProjective;
+use sha2::Sha256;
+use signature::BLSSigCore;
+
+/// Alias for the scalar type corresponding to a CurveProjective type
+type ScalarT<PtT> = <PtT as CurveProjective>::Scalar;
+
+/// A VRF whose proofs are BLS signatures under a VRF-specific tag
+pub trait BLSVrf<X: ExpandMsg>: BLSSigCore<X> + PointEncoding {
+    /// Tag for signing the input to make a proof
+    const CSUITE: &'static [u8];
+
+    /// Tag for hashing a proof to the output
+    const CSUITE_VRF: &'static [u8];
+
+    /// Evaluate the VRF on `alpha`
+    /// * input: the actual secret key x_prime
+    /// * input: the VRF input as bytes
+    /// * output: the 32-byte output beta
+    /// * output: the proof, which is serialized as the compressed signature
+    fn vrf_prove<B: AsRef<[u8]>>(x_prime: ScalarT<Self>, alpha: B) -> ([u8; 32], Self) {
+        let proof = <Self as BLSSigCore<X>>::core_sign(x_prime, alpha, Self::CSUITE);
+        (Self::vrf_proof_to_hash(&proof), proof)
+    }
+
+    /// Check a proof for `alpha` under `pk`. Returns beta if the proof is valid.
+    fn vrf_verify<B: AsRef<[u8]>>(
+        pk: Self::PKType,
+        alpha: B,
+        proof: Self,
+    ) -> Result<[u8; 32], BlsError> {
+        <Self as BLSSigCore<X>>::try_core_verify(pk, proof, alpha, Self::CSUITE)?;
+        Ok(Self::vrf_proof_to_hash(&proof))
+    }
+
+    /// The output beta for a proof, without checking the proof
+    fn vrf_proof_to_hash(proof: &Self) -> [u8; 32] {
+        let digest = X::expand_message(proof.to_compressed().as_ref(), Self::CSUITE_VRF, 32);
+        let mut ret = [0u8; 32];
+        ret.copy_from_slice(&digest[..]);
+        ret
+    }
+}
+
+impl BLSVrf<ExpandMsgXmd<Sha256>> for G1 {
+    const CSUITE: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_VRF_";
+    const CSUITE_VRF: &'static [u8] = b"BLS_VRF_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+}
+
+impl BLSVrf<ExpandMsgXmd<Sha256>> for G2 {
+    const CSUITE: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_VRF_";
+    const CSUITE_VRF: &'static [u8] = b"BLS_VRF_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+}